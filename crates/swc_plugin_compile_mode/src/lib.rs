@@ -25,13 +25,64 @@ impl SerdeDefault {
   fn template_tag_default() -> String {
     String::from("")
   }
+  fn render_fn_prefixes_default() -> Vec<String> {
+    vec![String::from("render")]
+  }
+  fn dynamic_id_attr_default() -> String {
+    String::from(utils::constants::DYNAMIC_ID)
+  }
 }
 
 #[derive(Deserialize, Debug)]
 pub struct ComponentReplace {
   pub current_init: String,
   pub dependency_define: String,
+  // 自定义的 @Builder 函数等，组件被使用时追加到模板末尾
+  #[serde(default)]
+  pub builder_define: String,
+}
+// 静态属性中 px 尺寸的转换策略
+#[derive(Deserialize, Debug, Default, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum UnitPolicy {
+  // 原样输出
+  #[default]
+  None,
+  // px 转换为 rpx，design_width 为设计稿宽度，换算比例为 750 / design_width
+  // 如设计稿宽度为 375 时，100px -> 200rpx
+  PxToRpx {
+    design_width: f64,
+  },
+}
+
+// design_width 是换算比例的除数，必须大于 0
+fn deserialize_unit_policy<'de, D>(deserializer: D) -> Result<UnitPolicy, D::Error>
+where
+  D: serde::Deserializer<'de>,
+{
+  let unit_policy = UnitPolicy::deserialize(deserializer)?;
+  if let UnitPolicy::PxToRpx { design_width } = unit_policy {
+    if design_width <= 0.0 {
+      return Err(serde::de::Error::custom(format!(
+        "unit_policy 的 design_width 必须大于 0，当前为 {}",
+        design_width
+      )));
+    }
+  }
+  Ok(unit_policy)
+}
+
+// 模板中原生组件标签名的大小写
+#[derive(Deserialize, Debug, Default, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum TagCase {
+  // scroll-view
+  #[default]
+  Lower,
+  // ScrollView
+  Pascal,
 }
+
 #[derive(Deserialize, Debug)]
 pub struct PluginConfig {
   pub tmpl_prefix: String,
@@ -55,6 +106,256 @@ pub struct PluginConfig {
   pub is_use_xs: bool,
   #[serde(default = "SerdeDefault::template_tag_default")]
   pub template_tag: String,
+  // 鸿蒙：有效子节点数超过该值时使用 createLazyChildren 渲染子树，0 表示不限制
+  #[serde(default)]
+  pub harmony_lazy_children_threshold: usize,
+  // 循环没有指定 compileForKey、回退到默认的 sid 时输出警告
+  #[serde(default)]
+  pub warn_loop_without_key: bool,
+  #[serde(default, deserialize_with = "deserialize_unit_policy")]
+  pub unit_policy: UnitPolicy,
+  // 以这些前缀命名的函数调用视为渲染函数，如 renderHeader()
+  #[serde(default = "SerdeDefault::render_fn_prefixes_default")]
+  pub render_fn_prefixes: Vec<String>,
+  // 由字面量与变量组成的简单字符串、数字运算生成内联 wxs 函数，在模板中计算，如 className={'btn-' + size}
+  // 只在支持内联 wxs 的平台生效
+  #[serde(default)]
+  pub inline_simple_exprs: bool,
+  // 组件缺少对应属性时注入的默认值：HashMap<组件名, HashMap<属性名, 默认值>>
+  #[serde(default)]
+  pub component_default_attrs: HashMap<String, HashMap<String, String>>,
+  // 把带有 compileMode 的 React.createElement 调用转换为 JSX 后再编译
+  #[serde(default)]
+  pub transform_create_element: bool,
+  // List 转换为 scroll-view 时，除内置白名单外额外透传的属性，如平台新增的 scroll-view 属性
+  #[serde(default)]
+  pub extra_scroll_view_props: Vec<String>,
+  // 把 onclick 等全小写的常见事件名识别为事件
+  #[serde(default)]
+  pub lowercase_events: bool,
+  // 鸿蒙：标记动态节点的属性名
+  #[serde(default = "SerdeDefault::dynamic_id_attr_default")]
+  pub dynamic_id_attr: String,
+  // @tarojs/components 中当前平台没有注册的组件改用该组件渲染，如 view；未配置时交由运行时渲染并给出提示
+  #[serde(default)]
+  pub unregistered_component_fallback: Option<String>,
+  // 模板节点的最大嵌套层级，超出时给出提示，0 表示不限制
+  #[serde(default)]
+  pub max_depth: usize,
+  // 把循环体提取为具名模板，循环中通过 <template is> 引用，减少重复输出
+  #[serde(default)]
+  pub hoist_loop_templates: bool,
+  // 循环中的属性使用内联的对象、数组字面量时输出警告，如 style={{ color: 'red' }}
+  #[serde(default)]
+  pub warn_loop_inline_objects: bool,
+  #[serde(default)]
+  pub tag_case: TagCase,
+  // 鸿蒙：生成的模板去掉缩进与空行，减小产物体积
+  #[serde(default)]
+  pub minify: bool,
+  // 没有指定 compileForKey 时，由循环体上的 key 推导循环的 key，同时删除非循环节点上无意义的 key。默认关闭以兼容旧版本
+  #[serde(default)]
+  pub key_as_loop_key: bool,
+}
+
+impl Default for PluginConfig {
+  // 与反序列化时各字段的默认值保持一致
+  fn default() -> Self {
+    Self {
+      tmpl_prefix: String::new(),
+      platform: SerdeDefault::platform_default(),
+      is_harmony: false,
+      components: HashMap::new(),
+      adapter: HashMap::new(),
+      support_events: vec![],
+      support_components: vec![],
+      event_adapter: HashMap::new(),
+      component_replace: HashMap::new(),
+      is_use_xs: SerdeDefault::is_use_xs_default(),
+      template_tag: SerdeDefault::template_tag_default(),
+      harmony_lazy_children_threshold: 0,
+      warn_loop_without_key: false,
+      unit_policy: UnitPolicy::None,
+      render_fn_prefixes: SerdeDefault::render_fn_prefixes_default(),
+      inline_simple_exprs: false,
+      component_default_attrs: HashMap::new(),
+      transform_create_element: false,
+      extra_scroll_view_props: vec![],
+      lowercase_events: false,
+      dynamic_id_attr: SerdeDefault::dynamic_id_attr_default(),
+      unregistered_component_fallback: None,
+      max_depth: 0,
+      hoist_loop_templates: false,
+      warn_loop_inline_objects: false,
+      tag_case: TagCase::Lower,
+      minify: false,
+      key_as_loop_key: false,
+    }
+  }
+}
+
+impl PluginConfig {
+  /**
+   * 在 Rust 中直接使用本插件时构造配置，未设置的字段取默认值
+   * PluginConfig::builder().tmpl_prefix("f0").platform("ALIPAY").build()
+   */
+  pub fn builder() -> PluginConfigBuilder {
+    PluginConfigBuilder::default()
+  }
+}
+
+#[derive(Default)]
+pub struct PluginConfigBuilder {
+  config: PluginConfig,
+}
+
+impl PluginConfigBuilder {
+  pub fn tmpl_prefix(mut self, tmpl_prefix: impl Into<String>) -> Self {
+    self.config.tmpl_prefix = tmpl_prefix.into();
+    self
+  }
+
+  pub fn platform(mut self, platform: impl Into<String>) -> Self {
+    self.config.platform = platform.into();
+    self
+  }
+
+  pub fn is_harmony(mut self, is_harmony: bool) -> Self {
+    self.config.is_harmony = is_harmony;
+    self
+  }
+
+  pub fn components(mut self, components: HashMap<String, HashMap<String, String>>) -> Self {
+    self.config.components = components;
+    self
+  }
+
+  pub fn adapter(mut self, adapter: HashMap<String, String>) -> Self {
+    self.config.adapter = adapter;
+    self
+  }
+
+  pub fn support_events(mut self, support_events: Vec<String>) -> Self {
+    self.config.support_events = support_events;
+    self
+  }
+
+  pub fn support_components(mut self, support_components: Vec<String>) -> Self {
+    self.config.support_components = support_components;
+    self
+  }
+
+  pub fn event_adapter(mut self, event_adapter: HashMap<String, String>) -> Self {
+    self.config.event_adapter = event_adapter;
+    self
+  }
+
+  pub fn component_replace(mut self, component_replace: HashMap<String, ComponentReplace>) -> Self {
+    self.config.component_replace = component_replace;
+    self
+  }
+
+  pub fn is_use_xs(mut self, is_use_xs: bool) -> Self {
+    self.config.is_use_xs = is_use_xs;
+    self
+  }
+
+  pub fn template_tag(mut self, template_tag: impl Into<String>) -> Self {
+    self.config.template_tag = template_tag.into();
+    self
+  }
+
+  pub fn harmony_lazy_children_threshold(mut self, harmony_lazy_children_threshold: usize) -> Self {
+    self.config.harmony_lazy_children_threshold = harmony_lazy_children_threshold;
+    self
+  }
+
+  pub fn warn_loop_without_key(mut self, warn_loop_without_key: bool) -> Self {
+    self.config.warn_loop_without_key = warn_loop_without_key;
+    self
+  }
+
+  pub fn unit_policy(mut self, unit_policy: UnitPolicy) -> Self {
+    self.config.unit_policy = unit_policy;
+    self
+  }
+
+  pub fn render_fn_prefixes(mut self, render_fn_prefixes: Vec<String>) -> Self {
+    self.config.render_fn_prefixes = render_fn_prefixes;
+    self
+  }
+
+  pub fn inline_simple_exprs(mut self, inline_simple_exprs: bool) -> Self {
+    self.config.inline_simple_exprs = inline_simple_exprs;
+    self
+  }
+
+  pub fn component_default_attrs(
+    mut self,
+    component_default_attrs: HashMap<String, HashMap<String, String>>,
+  ) -> Self {
+    self.config.component_default_attrs = component_default_attrs;
+    self
+  }
+
+  pub fn transform_create_element(mut self, transform_create_element: bool) -> Self {
+    self.config.transform_create_element = transform_create_element;
+    self
+  }
+
+  pub fn extra_scroll_view_props(mut self, extra_scroll_view_props: Vec<String>) -> Self {
+    self.config.extra_scroll_view_props = extra_scroll_view_props;
+    self
+  }
+
+  pub fn lowercase_events(mut self, lowercase_events: bool) -> Self {
+    self.config.lowercase_events = lowercase_events;
+    self
+  }
+
+  pub fn dynamic_id_attr(mut self, dynamic_id_attr: impl Into<String>) -> Self {
+    self.config.dynamic_id_attr = dynamic_id_attr.into();
+    self
+  }
+
+  pub fn unregistered_component_fallback(mut self, tag: impl Into<String>) -> Self {
+    self.config.unregistered_component_fallback = Some(tag.into());
+    self
+  }
+
+  pub fn max_depth(mut self, max_depth: usize) -> Self {
+    self.config.max_depth = max_depth;
+    self
+  }
+
+  pub fn hoist_loop_templates(mut self, hoist_loop_templates: bool) -> Self {
+    self.config.hoist_loop_templates = hoist_loop_templates;
+    self
+  }
+
+  pub fn warn_loop_inline_objects(mut self, warn_loop_inline_objects: bool) -> Self {
+    self.config.warn_loop_inline_objects = warn_loop_inline_objects;
+    self
+  }
+
+  pub fn tag_case(mut self, tag_case: TagCase) -> Self {
+    self.config.tag_case = tag_case;
+    self
+  }
+
+  pub fn minify(mut self, minify: bool) -> Self {
+    self.config.minify = minify;
+    self
+  }
+
+  pub fn key_as_loop_key(mut self, key_as_loop_key: bool) -> Self {
+    self.config.key_as_loop_key = key_as_loop_key;
+    self
+  }
+
+  pub fn build(self) -> PluginConfig {
+    self.config
+  }
 }
 
 /// An example plugin function with macro support.
@@ -1,5 +1,16 @@
-use super::{get_syntax_config, tr};
-use swc_core::ecma::transforms::testing::test;
+use super::{collect_diagnostics, get_syntax_config, get_template, get_visitor, parse_module, tr};
+use crate::UnitPolicy;
+use swc_core::{
+  common::errors::Level,
+  ecma::{
+    ast::{
+      CondExpr, Expr, Ident, JSXAttr, JSXAttrName, JSXAttrValue, JSXExpr, JSXExprContainer, Lit,
+      Str, Tpl,
+    },
+    transforms::testing::test,
+    visit::{Visit, VisitMutWith, VisitWith},
+  },
+};
 
 test!(
   get_syntax_config(),
@@ -53,3 +64,699 @@ test!(
       }
     "#
 );
+
+test!(
+  get_syntax_config(),
+  |_| tr(),
+  should_keep_slot_attr_verbatim,
+  r#"
+    import { Video } from '@tarojs/components'
+
+    function Index () {
+        return (
+          <View compileMode>
+            <Text slot="header">{title}</Text>
+            <Video slot="footer" src={src} />
+          </View>
+        )
+      }
+    "#
+);
+
+#[test]
+fn should_pass_through_raw_attrs() {
+  let tmpl = get_template(
+    &mut get_visitor(),
+    r#"
+    function Index () {
+      return (
+        <View compileMode>
+          <View raw:myProp="keep" hoverStartTime={time} />
+          <View raw:catchtouchmove="onMove" onClick={handleClick} />
+          <View raw:catchtap={handleTap} />
+        </View>
+      )
+    }
+    "#,
+  );
+  assert_eq!(
+    tmpl,
+    r#"<template name="tmpl_0_f0t0"><view><view hover-start-time="{{xs.b(i.cn[0].p2,50)}}" myProp="keep"></view><view bindtap="eh" catchtouchmove="onMove" data-sid="{{i.cn[1].sid}}" id="{{i.cn[1].sid}}"></view><view catchtap="{{i.cn[2].catchtap}}"></view></view></template>"#
+  );
+}
+
+#[test]
+fn should_self_close_void_elements() {
+  let tmpl = get_template(
+    &mut get_visitor(),
+    r#"
+    function Index () {
+      return (
+        <View compileMode>
+          <Image src={src} />
+          <View hoverClass={cls} />
+        </View>
+      )
+    }
+    "#,
+  );
+  assert_eq!(
+    tmpl,
+    r#"<template name="tmpl_0_f0t0"><view><image src="{{i.cn[0].p3}}"/><view hover-class="{{xs.b(i.cn[1].p1,'none')}}"></view></view></template>"#
+  );
+}
+
+#[test]
+fn should_keep_movable_view_attrs() {
+  let tmpl = get_template(
+    &mut get_visitor(),
+    r#"
+    function Index () {
+      return (
+        <View compileMode>
+          <MovableArea>
+            <MovableView catchMove disableScroll={disabled} />
+          </MovableArea>
+        </View>
+      )
+    }
+    "#,
+  );
+  assert_eq!(
+    tmpl,
+    r#"<template name="tmpl_0_f0t0"><view><movable-area><movable-view catch-move="true" disable-scroll="{{i.cn[0].cn[0].disableScroll}}"></movable-view></movable-area></view></template>"#
+  );
+}
+
+#[test]
+fn should_keep_text_user_select_by_default() {
+  let tmpl = get_template(
+    &mut get_visitor(),
+    r#"
+    function Index () {
+      return (
+        <View compileMode>
+          <Text userSelect>{content}</Text>
+          <Text selectable={selectable}>{content}</Text>
+        </View>
+      )
+    }
+    "#,
+  );
+  assert_eq!(
+    tmpl,
+    r#"<template name="tmpl_0_f0t0"><view><text user-select="true">{{i.cn[0].cn[0].v}}</text><text selectable="{{!!xs.b(i.cn[1].p1,!1)&&xs.b(i.cn[1].p1,!1)!=='false'}}">{{i.cn[1].cn[0].v}}</text></view></template>"#
+  );
+}
+
+#[test]
+fn should_convert_text_user_select_on_alipay() {
+  let mut visitor = get_visitor();
+  visitor.config.platform = String::from("ALIPAY");
+  let tmpl = get_template(
+    &mut visitor,
+    r#"
+    function Index () {
+      return (
+        <View compileMode>
+          <Text userSelect>{content}</Text>
+          <Text userSelect={selectable}>{content}</Text>
+        </View>
+      )
+    }
+    "#,
+  );
+  assert_eq!(
+    tmpl,
+    r#"<template name="tmpl_0_f0t0"><view><text selectable="true">{{i.cn[0].cn[0].v}}</text><text selectable="{{!!xs.b(i.cn[1].p1,!1)&&xs.b(i.cn[1].p1,!1)!=='false'}}">{{i.cn[1].cn[0].v}}</text></view></template>"#
+  );
+}
+
+#[test]
+fn should_escape_platform_reserved_attrs() {
+  let input = r#"
+    function Index () {
+      return (
+        <View compileMode>
+          <View sFor="list" sKey={key}>{content}</View>
+        </View>
+      )
+    }
+    "#;
+  let mut visitor = get_visitor();
+  let tmpl = get_template(&mut visitor, input);
+  assert_eq!(
+    tmpl,
+    r#"<template name="tmpl_0_f0t0"><view><view s-for="list" s-key="{{i.cn[0].sKey}}">{{i.cn[0].cn[0].v}}</view></view></template>"#
+  );
+
+  let mut visitor = get_visitor();
+  visitor.config.platform = String::from("SWAN");
+  let tmpl = get_template(&mut visitor, input);
+  assert_eq!(
+    tmpl,
+    r#"<template name="tmpl_0_f0t0"><view><view data-s-for="list" data-s-key="{{i.cn[0].sKey}}">{{i.cn[0].cn[0].v}}</view></view></template>"#
+  );
+}
+
+#[test]
+fn should_support_authored_catch_events() {
+  let input = r#"
+    function Index () {
+      return (
+        <View compileMode>
+          <View catchTap={handleTap} />
+          <View catch:touchmove={handleMove} />
+          <View catchTouchStart="onStart" />
+        </View>
+      )
+    }
+    "#;
+  let tmpl = get_template(&mut get_visitor(), input);
+  assert_eq!(
+    tmpl,
+    r#"<template name="tmpl_0_f0t0"><view><view catchtap="eh" data-sid="{{i.cn[0].sid}}" id="{{i.cn[0].sid}}"></view><view catchtouchmove="eh" data-sid="{{i.cn[1].sid}}" id="{{i.cn[1].sid}}"></view><view catchtouchstart="onStart"></view></view></template>"#
+  );
+
+  let mut visitor = get_visitor();
+  visitor.config.platform = String::from("ALIPAY");
+  let tmpl = get_template(&mut visitor, input);
+  assert_eq!(
+    tmpl,
+    r#"<template name="tmpl_0_f0t0"><view><view catchTap="eh" data-sid="{{i.cn[0].sid}}" id="{{i.cn[0].sid}}"></view><view catchTouchMove="eh" data-sid="{{i.cn[1].sid}}" id="{{i.cn[1].sid}}"></view><view catchTouchStart="onStart"></view></view></template>"#
+  );
+}
+
+#[test]
+fn should_convert_px_to_rpx_by_design_width() {
+  let input = r#"
+    function Index () {
+      return (
+        <View compileMode>
+          <View style="width: 100px; margin: 0 7.5px;" hoverClass="w-100px">{content}</View>
+        </View>
+      )
+    }
+    "#;
+  let mut visitor = get_visitor();
+  visitor.config.unit_policy = UnitPolicy::PxToRpx {
+    design_width: 375.0,
+  };
+  assert_eq!(
+    get_template(&mut visitor, input),
+    r#"<template name="tmpl_0_f0t0"><view><view hover-class="w-100px" style="width: 200rpx; margin: 0 15rpx;">{{i.cn[0].cn[0].v}}</view></view></template>"#
+  );
+
+  assert_eq!(
+    get_template(&mut get_visitor(), input),
+    r#"<template name="tmpl_0_f0t0"><view><view hover-class="w-100px" style="width: 100px; margin: 0 7.5px;">{{i.cn[0].cn[0].v}}</view></view></template>"#
+  );
+}
+
+#[test]
+fn should_support_touch_force_change() {
+  let input = r#"
+    function Index () {
+      return (
+        <View compileMode>
+          <View onTouchForceChange={handleForceChange}>{content}</View>
+        </View>
+      )
+    }
+    "#;
+  let mut visitor = get_visitor();
+  assert!(collect_diagnostics(&mut visitor, input).is_empty());
+  assert_eq!(
+    visitor.templates.get("f0t0").unwrap(),
+    r#"<template name="tmpl_0_f0t0"><view><view bindtouchforcechange="eh" data-sid="{{i.cn[0].sid}}" id="{{i.cn[0].sid}}">{{i.cn[0].cn[0].v}}</view></view></template>"#
+  );
+
+  let mut visitor = get_visitor();
+  visitor.config.platform = String::from("ALIPAY");
+  assert_eq!(
+    collect_diagnostics(&mut visitor, input),
+    vec![(
+      Level::Warning,
+      String::from("当前平台不支持 onTouchForceChange 事件")
+    )]
+  );
+}
+
+#[test]
+fn should_join_static_class_array() {
+  let tmpl = get_template(
+    &mut get_visitor(),
+    r#"
+    function Index () {
+      return (
+        <View compileMode>
+          <View className={['a', 'b']}>{content}</View>
+        </View>
+      )
+    }
+    "#,
+  );
+  assert_eq!(
+    tmpl,
+    r#"<template name="tmpl_0_f0t0"><view><view class="a b">{{i.cn[0].cn[0].v}}</view></view></template>"#
+  );
+}
+
+#[test]
+fn should_keep_ref_out_of_template() {
+  struct RefCollector(Vec<String>);
+  impl Visit for RefCollector {
+    fn visit_jsx_attr(&mut self, attr: &JSXAttr) {
+      if let JSXAttrName::Ident(Ident { sym, .. }) = &attr.name {
+        self.0.push(sym.to_string());
+      }
+    }
+  }
+
+  let mut module = parse_module(
+    r#"
+    function Index () {
+      return (
+        <View compileMode>
+          <View ref={el => (this.box = el)}>{content}</View>
+          <View ref={boxRef} />
+        </View>
+      )
+    }
+    "#,
+  );
+  let mut visitor = get_visitor();
+  module.visit_mut_with(&mut visitor);
+  assert_eq!(
+    visitor.templates.get("f0t0").unwrap(),
+    r#"<template name="tmpl_0_f0t0"><view><view>{{i.cn[0].cn[0].v}}</view><view></view></view></template>"#
+  );
+
+  // ref 仍保留在 JSX 中，运行时可以拿到对应节点
+  let mut collector = RefCollector(vec![]);
+  module.visit_with(&mut collector);
+  assert_eq!(collector.0.iter().filter(|name| *name == "ref").count(), 2);
+}
+
+#[test]
+fn should_inline_simple_exprs() {
+  let input = r#"
+    function Index () {
+      return (
+        <View compileMode>
+          <View className={'btn btn-' + size}>A</View>
+          <View className={`tag tag-${this.state.type}`} hoverClass={getHoverClass()}>B</View>
+        </View>
+      )
+    }
+    "#;
+  let tmpl = get_template(&mut get_visitor(), input);
+  assert_eq!(
+    tmpl,
+    r#"<template name="tmpl_0_f0t0"><view><view class="{{i.cn[0].cl}}">A</view><view class="{{i.cn[1].cl}}" hover-class="{{xs.b(i.cn[1].p1,'none')}}">B</view></view></template>"#
+  );
+
+  let mut visitor = get_visitor();
+  visitor.config.inline_simple_exprs = true;
+  let tmpl = get_template(&mut visitor, input);
+  assert_eq!(
+    tmpl,
+    r#"<template name="tmpl_0_f0t0"><wxs module="ixs">module.exports={f0:function(xs0){return 'btn btn-'+xs0},f1:function(xs0){return 'tag tag-'+(xs0)}}</wxs><view><view class="{{ixs.f0(i.cn[0].xs0)}}">A</view><view class="{{ixs.f1(i.cn[1].xs0)}}" hover-class="{{xs.b(i.cn[1].p1,'none')}}">B</view></view></template>"#
+  );
+
+  // 不支持内联 wxs 的平台交由运行时计算
+  let mut visitor = get_visitor();
+  visitor.config.inline_simple_exprs = true;
+  visitor.config.platform = String::from("ALIPAY");
+  assert!(!get_template(&mut visitor, input).contains("ixs"));
+}
+
+#[test]
+fn should_check_platform_support_of_transition_events() {
+  let input = r#"
+    function Index () {
+      return (
+        <View compileMode>
+          <View onTransitionEnd={handleTransitionEnd}>{content}</View>
+        </View>
+      )
+    }
+    "#;
+  let mut visitor = get_visitor();
+  assert!(collect_diagnostics(&mut visitor, input).is_empty());
+  assert_eq!(
+    visitor.templates.get("f0t0").unwrap(),
+    r#"<template name="tmpl_0_f0t0"><view><view bindtransitionend="eh" data-sid="{{i.cn[0].sid}}" id="{{i.cn[0].sid}}">{{i.cn[0].cn[0].v}}</view></view></template>"#
+  );
+
+  let mut visitor = get_visitor();
+  visitor.config.platform = String::from("SWAN");
+  assert_eq!(
+    collect_diagnostics(&mut visitor, input),
+    vec![(
+      Level::Warning,
+      String::from("当前平台不支持 onTransitionEnd 事件")
+    )]
+  );
+}
+
+#[test]
+fn should_remove_empty_class_and_style() {
+  let tmpl = get_template(
+    &mut get_visitor(),
+    r#"
+    function Index () {
+      return (
+        <View compileMode>
+          <View className="" style="">A</View>
+          <View className={cls} style={''}>B</View>
+          <View className={[]}>C</View>
+        </View>
+      )
+    }
+    "#,
+  );
+  assert_eq!(
+    tmpl,
+    r#"<template name="tmpl_0_f0t0"><view><view>A</view><view class="{{i.cn[0].cl}}">B</view><view>C</view></view></template>"#
+  );
+}
+
+#[test]
+fn should_keep_authored_bind_events() {
+  let tmpl = get_template(
+    &mut get_visitor(),
+    r#"
+    function Index () {
+      return (
+        <View compileMode>
+          <View bindtap={handleTap}>{content}</View>
+          <View bind:longpress={handleLongPress}></View>
+          <View bindtouchstart="onStart"></View>
+        </View>
+      )
+    }
+    "#,
+  );
+  assert_eq!(
+    tmpl,
+    r#"<template name="tmpl_0_f0t0"><view><view bindtap="eh" data-sid="{{i.cn[0].sid}}" id="{{i.cn[0].sid}}">{{i.cn[0].cn[0].v}}</view><view bind:longpress="eh" data-sid="{{i.cn[1].sid}}" id="{{i.cn[1].sid}}"></view><view bindtouchstart="onStart"></view></view></template>"#
+  );
+}
+
+#[test]
+fn should_coerce_boolean_attrs() {
+  let tmpl = get_template(
+    &mut get_visitor(),
+    r#"
+    function Index () {
+      return (
+        <View compileMode>
+          <View hoverStopPropagation={stop} hoverStartTime={time}>{content}</View>
+          <View hoverStopPropagation="false">{title}</View>
+        </View>
+      )
+    }
+    "#,
+  );
+  assert_eq!(
+    tmpl,
+    r#"<template name="tmpl_0_f0t0"><view><view hover-start-time="{{xs.b(i.cn[0].p2,50)}}" hover-stop-propagation="{{!!xs.b(i.cn[0].p4,!1)&&xs.b(i.cn[0].p4,!1)!=='false'}}">{{i.cn[0].cn[0].v}}</view><view hover-stop-propagation="{{false}}">{{i.cn[1].cn[0].v}}</view></view></template>"#
+  );
+}
+
+#[test]
+fn should_skip_attrs_listed_in_compile_ignore() {
+  let tmpl = get_template(
+    &mut get_visitor(),
+    r#"
+    function Index () {
+      return (
+        <View compileMode>
+          <View compileIgnore="onClick, style" onClick={handleClick} style={style} className={cls}>{content}</View>
+        </View>
+      )
+    }
+    "#,
+  );
+  assert_eq!(
+    tmpl,
+    r#"<template name="tmpl_0_f0t0"><view><view class="{{i.cn[0].cl}}">{{i.cn[0].cn[0].v}}</view></view></template>"#
+  );
+}
+
+#[test]
+fn should_recognize_lowercase_events_when_enabled() {
+  let input = r#"
+    function Index () {
+      return (
+        <View compileMode>
+          <View onclick={handleClick}></View>
+        </View>
+      )
+    }
+    "#;
+  let mut visitor = get_visitor();
+  visitor.config.lowercase_events = true;
+  assert_eq!(
+    get_template(&mut visitor, input),
+    r#"<template name="tmpl_0_f0t0"><view><view bindtap="eh" data-sid="{{i.cn[0].sid}}" id="{{i.cn[0].sid}}"></view></view></template>"#
+  );
+}
+
+#[test]
+fn should_bind_map_object_props_and_events() {
+  let input = r#"
+    import { Map } from '@tarojs/components'
+    function Index () {
+      return (
+        <View compileMode>
+          <Map longitude={lng} latitude={lat} markers={[{ id: 0, latitude: lat, longitude: lng }]} polyline={lines} circles={circles} data-id="map" hoverClass="active" onClick={handleClick} onMarkerTap={handleMarkerTap} />
+        </View>
+      )
+    }
+    "#;
+  assert_eq!(
+    get_template(&mut get_visitor(), input),
+    r#"<template name="tmpl_0_f0t0"><view><map bindmarkertap="eh" bindtap="eh" circles="{{i.cn[0].p4||[]}}" data-id="map" data-sid="{{i.cn[0].sid}}" hover-class="active" id="{{i.cn[0].sid}}" latitude="{{i.cn[0].p1}}" longitude="{{i.cn[0].p0}}" markers="{{i.cn[0].p2||[]}}" polyline="{{i.cn[0].p3||[]}}"></map></view></template>"#
+  );
+
+  let mut visitor = get_visitor();
+  visitor.config.platform = String::from("ALIPAY");
+  assert!(get_template(&mut visitor, input).contains(r#"onMarkerTap="eh""#));
+}
+
+#[test]
+fn should_lower_optional_chaining_in_bindings() {
+  let tmpl = get_template(
+    &mut get_visitor(),
+    r#"
+    function Index () {
+      return (
+        <View compileMode>
+          {list.map(item => <View compileForKey="item?.id" data-first="{{item.tags[0]?.name}}" data-name="{{item?.name?.first}}">{item.text}</View>)}
+        </View>
+      )
+    }
+    "#,
+  );
+  assert_eq!(
+    tmpl,
+    r#"<template name="tmpl_0_f0t0"><view><view data-first="{{(item.tags[0]&&item.tags[0].name)}}" data-name="{{(item&&item.name&&item.name.first)}}" wx:for="{{i.cn}}" wx:key="{{(item&&item.id)}}">{{item.cn[0].v}}</view></view></template>"#
+  );
+}
+
+#[test]
+fn should_lower_nullish_coalescing_in_bindings() {
+  let input = r#"
+    function Index () {
+      return (
+        <View compileMode>
+          <View data-label="{{item.label ?? 'default'}}">{content}</View>
+        </View>
+      )
+    }
+    "#;
+  assert_eq!(
+    get_template(&mut get_visitor(), input),
+    r#"<template name="tmpl_0_f0t0"><view><view data-label="{{(item.label!=null?item.label:'default')}}">{{i.cn[0].cn[0].v}}</view></view></template>"#
+  );
+
+  let mut visitor = get_visitor();
+  visitor.config.platform = String::from("H5");
+  assert!(get_template(&mut visitor, input).contains(r#"data-label="{{item.label ?? 'default'}}""#));
+}
+
+#[test]
+fn should_keep_mark_attrs() {
+  assert_eq!(
+    get_template(
+      &mut get_visitor(),
+      r#"
+      function Index () {
+        return (
+          <View compileMode>
+            <View markId="banner" mark-group="top" />
+            <View markId={id} onClick={handleClick} />
+          </View>
+        )
+      }
+      "#,
+    ),
+    r#"<template name="tmpl_0_f0t0"><view><view mark:group="top" mark:id="banner"></view><view bindtap="eh" data-sid="{{i.cn[0].sid}}" id="{{i.cn[0].sid}}" mark:id="{{i.cn[0].markId}}"></view></view></template>"#
+  );
+}
+
+#[test]
+fn should_keep_lazy_load_attrs_of_image() {
+  assert_eq!(
+    get_template(
+      &mut get_visitor(),
+      r#"
+      import { Image } from '@tarojs/components'
+
+      function Index () {
+        return (
+          <View compileMode>
+            <Image src={src} lazyLoad={lazy} showMenuByLongpress />
+          </View>
+        )
+      }
+      "#,
+    ),
+    r#"<template name="tmpl_0_f0t0"><view><image lazy-load="{{!!xs.b(i.cn[0].p0,!1)&&xs.b(i.cn[0].p0,!1)!=='false'}}" show-menu-by-longpress="true" src="{{i.cn[0].p3}}"/></view></template>"#
+  );
+}
+
+#[test]
+fn should_join_class_names_call_with_string_args() {
+  let tmpl = get_template(
+    &mut get_visitor(),
+    r#"
+    function Index () {
+      return (
+        <View compileMode>
+          <View className={classNames('a', 'b')}>{content}</View>
+        </View>
+      )
+    }
+    "#,
+  );
+  assert_eq!(
+    tmpl,
+    r#"<template name="tmpl_0_f0t0"><view><view class="a b">{{i.cn[0].cn[0].v}}</view></view></template>"#
+  );
+}
+
+#[test]
+fn should_lower_class_names_call_with_object_arg() {
+  // 记录 className 的值：模板字符串中各个条件类名，或者保留下来的调用
+  struct ClassCollector(Vec<String>);
+  impl Visit for ClassCollector {
+    fn visit_jsx_attr(&mut self, attr: &JSXAttr) {
+      if let JSXAttr {
+        name: JSXAttrName::Ident(Ident { sym, .. }),
+        value:
+          Some(JSXAttrValue::JSXExprContainer(JSXExprContainer {
+            expr: JSXExpr::Expr(expr),
+            ..
+          })),
+        ..
+      } = attr
+      {
+        if sym != "className" {
+          return;
+        }
+        match &**expr {
+          Expr::Tpl(Tpl { exprs, quasis, .. }) => {
+            exprs.iter().for_each(|expr| {
+              if let Expr::Cond(CondExpr { cons, .. }) = &**expr {
+                if let Expr::Lit(Lit::Str(Str { value, .. })) = &**cons {
+                  self.0.push(value.to_string());
+                }
+              }
+            });
+            self.0.push(quasis.last().unwrap().raw.to_string());
+          }
+          Expr::Call(..) => self.0.push(String::from("call")),
+          _ => (),
+        }
+      }
+    }
+  }
+
+  let mut module = parse_module(
+    r#"
+    function Index () {
+      return (
+        <View compileMode>
+          <View className={cx('btn', { active: isActive, 'is-disabled': disabled })}>{a}</View>
+          <View className={cx(base, { active: isActive })}>{b}</View>
+        </View>
+      )
+    }
+    "#,
+  );
+  module.visit_mut_with(&mut get_visitor());
+  let mut collector = ClassCollector(vec![]);
+  module.visit_with(&mut collector);
+  assert_eq!(
+    collector.0,
+    vec![
+      String::from("active"),
+      String::from("is-disabled"),
+      String::from(" btn"),
+      // 无法识别的参数保持原样
+      String::from("call"),
+    ]
+  );
+}
+
+#[test]
+fn should_validate_button_open_type() {
+  let input = r#"
+    function Index () {
+      return (
+        <View compileMode>
+          <Button openType="getUserInfo" onGetUserInfo={handleUserInfo}>login</Button>
+          <Button openType="getUserinfo">login</Button>
+        </View>
+      )
+    }
+    "#;
+  let mut visitor = get_visitor();
+  assert_eq!(
+    collect_diagnostics(&mut visitor, input),
+    vec![(
+      Level::Warning,
+      String::from("当前平台的 Button 不支持 open-type=\"getUserinfo\"")
+    )]
+  );
+  assert_eq!(
+    visitor.templates.get("f0t0").unwrap(),
+    r#"<template name="tmpl_0_f0t0"><view><button bindgetuserinfo="eh" data-sid="{{i.cn[0].sid}}" id="{{i.cn[0].sid}}" open-type="getUserInfo">login</button><button open-type="getUserinfo">login</button></view></template>"#
+  );
+}
+
+#[test]
+fn should_use_configured_event_prefix() {
+  let input = r#"
+    function Index () {
+      return (
+        <View compileMode>
+          <View onClick={handleClick}></View>
+          <Input onFocus={handleFocus} />
+        </View>
+      )
+    }
+    "#;
+  let mut visitor = get_visitor();
+  visitor
+    .config
+    .adapter
+    .insert(String::from("eventPrefix"), String::from("bind:"));
+  assert_eq!(
+    get_template(&mut visitor, input),
+    r#"<template name="tmpl_0_f0t0"><view><view bind:tap="eh" data-sid="{{i.cn[0].sid}}" id="{{i.cn[0].sid}}"></view><input bind:focus="eh" data-sid="{{i.cn[1].sid}}" id="{{i.cn[1].sid}}"/></view></template>"#
+  );
+}
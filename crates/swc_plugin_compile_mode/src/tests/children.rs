@@ -1,5 +1,7 @@
-use super::{get_syntax_config, tr};
-use swc_core::ecma::transforms::testing::test;
+use super::{collect_diagnostics, get_syntax_config, get_template, get_visitor, tr};
+use crate::{transform::TransformVisitor, TagCase};
+use std::collections::HashMap;
+use swc_core::{common::errors::Level, ecma::transforms::testing::test};
 
 test!(
   get_syntax_config(),
@@ -108,3 +110,692 @@ test!(
     }
     "#
 );
+
+#[test]
+fn should_unwrap_block_with_single_child() {
+  let tmpl = get_template(
+    &mut get_visitor(),
+    r#"
+    function Index () {
+      return (
+        <View compileMode>
+          <Block>
+            <View>{content}</View>
+          </Block>
+        </View>
+      )
+    }
+    "#,
+  );
+  assert_eq!(
+    tmpl,
+    r#"<template name="tmpl_0_f0t0"><view><view>{{i.cn[0].cn[0].cn[0].v}}</view></view></template>"#
+  );
+}
+
+#[test]
+fn should_keep_block_with_multiple_children() {
+  let tmpl = get_template(
+    &mut get_visitor(),
+    r#"
+    function Index () {
+      return (
+        <View compileMode>
+          <Block>
+            <View>{header}</View>
+            <View>{footer}</View>
+          </Block>
+        </View>
+      )
+    }
+    "#,
+  );
+  assert_eq!(
+    tmpl,
+    r#"<template name="tmpl_0_f0t0"><view><block><view>{{i.cn[0].cn[0].cn[0].v}}</view><view>{{i.cn[0].cn[1].cn[0].v}}</view></block></view></template>"#
+  );
+}
+
+#[test]
+fn should_render_custom_wrapper_natively() {
+  let tmpl = get_template(
+    &mut get_visitor(),
+    r#"
+    import { CustomWrapper } from '@tarojs/components'
+
+    function Index () {
+      return (
+        <View compileMode>
+          <View>{title}</View>
+          <CustomWrapper>
+            <View>{content}</View>
+          </CustomWrapper>
+        </View>
+      )
+    }
+    "#,
+  );
+  assert_eq!(
+    tmpl,
+    r#"<template name="tmpl_0_f0t0"><view><view>{{i.cn[0].cn[0].v}}</view><custom-wrapper i="{{i.cn[1]}}" l="{{l}}" id="{{i.cn[1].uid||i.cn[1].sid}}" data-sid="{{i.cn[1].sid}}"></custom-wrapper></view></template>"#
+  );
+}
+
+#[test]
+fn should_expand_jsx_array_as_siblings() {
+  let tmpl = get_template(
+    &mut get_visitor(),
+    r#"
+    function Index () {
+      return (
+        <View compileMode>
+          <View>{title}</View>
+          {[<View>{a}</View>, (<Text>{b}</Text>)]}
+        </View>
+      )
+    }
+    "#,
+  );
+  assert_eq!(
+    tmpl,
+    r#"<template name="tmpl_0_f0t0"><view><view>{{i.cn[0].cn[0].v}}</view><view>{{i.cn[1].cn[0].v}}</view><text>{{i.cn[2].cn[0].v}}</text></view></template>"#
+  );
+}
+
+#[test]
+fn should_support_canvas() {
+  let tmpl = get_template(
+    &mut get_visitor(),
+    r#"
+    import { Canvas } from '@tarojs/components'
+
+    function Index () {
+      return (
+        <View compileMode>
+          <Canvas canvasId="chart" onTouchStart={handleTouchStart} />
+          <Canvas id="board" type="webgl" />
+          <Canvas onLongPress={handleLongPress} />
+        </View>
+      )
+    }
+    "#,
+  );
+  assert_eq!(
+    tmpl,
+    r#"<template name="tmpl_0_f0t0"><view><canvas bindtouchstart="eh" canvas-id="chart" data-sid="{{i.cn[0].sid}}" id="chart" type="2d"></canvas><canvas canvas-id="board" id="board" type="webgl"></canvas><canvas bindlongtap="eh" data-sid="{{i.cn[1].sid}}" id="{{i.cn[1].sid}}" type="2d"></canvas></view></template>"#
+  );
+}
+
+#[test]
+fn should_convert_inner_html_to_rich_text() {
+  let tmpl = get_template(
+    &mut get_visitor(),
+    r#"
+    function Index () {
+      return (
+        <View compileMode>
+          <View className="article" dangerouslySetInnerHTML={{ __html: html }}>
+            <Text>placeholder</Text>
+          </View>
+        </View>
+      )
+    }
+    "#,
+  );
+  assert_eq!(
+    tmpl,
+    r#"<template name="tmpl_0_f0t0"><view><rich-text class="article" nodes="{{xs.b(i.cn[0].p0,[])}}"></rich-text></view></template>"#
+  );
+}
+
+#[test]
+fn should_keep_video_media_events() {
+  let mut visitor = get_visitor();
+  let input = r#"
+    import { Video } from '@tarojs/components'
+
+    function Index () {
+      return (
+        <View compileMode>
+          <Video
+            src={src}
+            poster="cover.png"
+            data-id="video"
+            onTap={handleTap}
+            onTimeUpdate={handleTimeUpdate}
+            onEnded={handleEnded}
+            onScroll={handleScroll}
+            hoverClass="active"
+          />
+        </View>
+      )
+    }
+    "#;
+  assert_eq!(
+    collect_diagnostics(&mut visitor, input),
+    vec![(
+      Level::Warning,
+      String::from("Video 不支持 hoverClass 属性，已忽略")
+    )]
+  );
+  assert_eq!(
+    visitor.templates.get("f0t0").unwrap(),
+    r#"<template name="tmpl_0_f0t0"><view><video bindended="eh" bindscroll="eh" bindtap="eh" bindtimeupdate="eh" data-id="video" data-sid="{{i.cn[0].sid}}" id="{{i.cn[0].sid}}" poster="cover.png" src="{{i.cn[0].src}}"></video></view></template>"#
+  );
+}
+
+#[test]
+fn should_support_custom_render_fn_prefixes() {
+  let input = r#"
+    function Index () {
+      return (
+        <View compileMode>
+          <View>{renderHeader()}</View>
+          <View>{this.makeFooter()}</View>
+        </View>
+      )
+    }
+    "#;
+  let tmpl = get_template(&mut get_visitor(), input);
+  assert_eq!(
+    tmpl,
+    r#"<template name="tmpl_0_f0t0"><view><view><template is="{{xs.a(c, i.cn[0].cn[0].nn, l)}}" data="{{i:i.cn[0].cn[0],c:c+1,l:xs.f(l,i.cn[0].cn[0].nn)}}" /></view><view>{{i.cn[1].cn[0].v}}</view></view></template>"#
+  );
+
+  let mut visitor = get_visitor();
+  visitor.config.render_fn_prefixes = vec![String::from("render"), String::from("make")];
+  let tmpl = get_template(&mut visitor, input);
+  assert_eq!(
+    tmpl,
+    r#"<template name="tmpl_0_f0t0"><view><view><template is="{{xs.a(c, i.cn[0].cn[0].nn, l)}}" data="{{i:i.cn[0].cn[0],c:c+1,l:xs.f(l,i.cn[0].cn[0].nn)}}" /></view><view><template is="{{xs.a(c, i.cn[1].cn[0].nn, l)}}" data="{{i:i.cn[1].cn[0],c:c+1,l:xs.f(l,i.cn[1].cn[0].nn)}}" /></view></view></template>"#
+  );
+}
+
+#[test]
+fn should_reuse_cached_static_fragments() {
+  let input = r#"
+    function Index () {
+      return (
+        <View compileMode>
+          <View className="card"><Text>hello</Text></View>
+          <View>{content}</View>
+        </View>
+      )
+    }
+    "#;
+  let mut visitor = get_visitor();
+  let tmpl = get_template(&mut visitor, input);
+  assert_eq!(
+    tmpl,
+    r#"<template name="tmpl_0_f0t0"><view><view class="card"><text>hello</text></view><view>{{i.cn[0].cn[0].v}}</view></view></template>"#
+  );
+  let cache = visitor.static_fragment_cache.clone();
+  assert_eq!(cache.borrow().len(), 2);
+
+  // 共享缓存的 visitor 输出完全一致
+  let mut next = get_visitor();
+  next.static_fragment_cache = cache.clone();
+  assert_eq!(get_template(&mut next, input), tmpl);
+
+  // 命中缓存时直接使用缓存内容，不会重新生成
+  cache
+    .borrow_mut()
+    .values_mut()
+    .for_each(|fragment| *fragment = String::from("<cached/>"));
+  let mut next = get_visitor();
+  next.static_fragment_cache = cache.clone();
+  assert_eq!(
+    get_template(&mut next, input),
+    r#"<template name="tmpl_0_f0t0"><view><cached/><view>{{i.cn[0].cn[0].v}}</view></view></template>"#
+  );
+
+  // 配置不同的 visitor 不会命中彼此的缓存
+  let mut next = get_visitor();
+  next.config.platform = String::from("ALIPAY");
+  next.static_fragment_cache = cache.clone();
+  assert_eq!(
+    get_template(&mut next, input),
+    r#"<template name="tmpl_0_f0t0"><view><view class="card"><text>hello</text></view><view>{{i.cn[0].cn[0].v}}</view></view></template>"#
+  );
+  assert_eq!(cache.borrow().len(), 4);
+}
+
+#[test]
+fn should_not_cache_static_fragments_with_diagnostics() {
+  let input = r#"
+    function Index () {
+      return (
+        <View compileMode>
+          <View><Button openType="getUserinfo">login</Button></View>
+          <View><Button openType="getUserinfo">login</Button></View>
+        </View>
+      )
+    }
+    "#;
+  let mut visitor = get_visitor();
+  let diagnostics = collect_diagnostics(&mut visitor, input);
+  assert_eq!(diagnostics.len(), 2);
+  assert!(visitor.static_fragment_cache.borrow().is_empty());
+
+  let mut visitor = get_visitor();
+  visitor.config.max_depth = 3;
+  let input = r#"
+    function Index () {
+      return (
+        <View compileMode>
+          <View><View><View>deep</View></View></View>
+          <View><View><View>deep</View></View></View>
+        </View>
+      )
+    }
+    "#;
+  let diagnostics = collect_diagnostics(&mut visitor, input);
+  assert_eq!(diagnostics.len(), 2);
+  assert!(visitor.static_fragment_cache.borrow().is_empty());
+}
+
+#[test]
+fn should_match_components_registered_in_pascal_case() {
+  let mut config = get_visitor().config;
+  config.components.insert(
+    String::from("MyComp"),
+    HashMap::from([(String::from("title"), String::from("i.p0"))]),
+  );
+  let tmpl = get_template(
+    &mut TransformVisitor::new(config),
+    r#"
+    function Index () {
+      return (
+        <View compileMode>
+          <MyComp title="hi" />
+          <MyComp title={title} />
+        </View>
+      )
+    }
+    "#,
+  );
+  assert_eq!(
+    tmpl,
+    r#"<template name="tmpl_0_f0t0"><view><my-comp title="hi"></my-comp><my-comp title="{{i.cn[0].p0}}"></my-comp></view></template>"#
+  );
+}
+
+#[test]
+fn should_inject_component_default_attrs() {
+  let mut config = get_visitor().config;
+  config.components.insert(
+    String::from("my-comp"),
+    HashMap::from([(String::from("size"), String::from("i.p0"))]),
+  );
+  config.component_default_attrs.insert(
+    String::from("MyComp"),
+    HashMap::from([(String::from("size"), String::from("default"))]),
+  );
+  let tmpl = get_template(
+    &mut TransformVisitor::new(config),
+    r#"
+    function Index () {
+      return (
+        <View compileMode>
+          <MyComp />
+          <MyComp size="mini" />
+          <MyComp size={size} />
+          <View />
+        </View>
+      )
+    }
+    "#,
+  );
+  assert_eq!(
+    tmpl,
+    r#"<template name="tmpl_0_f0t0"><view><my-comp size="default"></my-comp><my-comp size="mini"></my-comp><my-comp size="{{i.cn[0].p0}}"></my-comp><view></view></view></template>"#
+  );
+}
+
+#[test]
+fn should_render_dynamic_strategy_subtree_at_runtime() {
+  let tmpl = get_template(
+    &mut get_visitor(),
+    r#"
+    function Index () {
+      return (
+        <View compileMode>
+          <View compileMode="dynamic" className="box">
+            <Text>{content}</Text>
+          </View>
+          <View>{title}</View>
+        </View>
+      )
+    }
+    "#,
+  );
+  assert_eq!(
+    tmpl,
+    r#"<template name="tmpl_0_f0t0"><view><template is="{{xs.a(c, i.cn[0].nn, l)}}" data="{{i:i.cn[0],c:c+1,l:xs.f(l,i.cn[0].nn)}}" /><view>{{i.cn[1].cn[0].v}}</view></view></template>"#
+  );
+}
+
+#[test]
+fn should_compile_static_strategy_subtree_into_template() {
+  let input = r#"
+    function Index () {
+      return (
+        <View compileMode>
+          <View compileMode="static" key="banner" className="banner">
+            <Text>hello</Text>
+          </View>
+          <View>{title}</View>
+        </View>
+      )
+    }
+    "#;
+  assert_eq!(
+    get_template(&mut get_visitor(), input),
+    r#"<template name="tmpl_0_f0t0"><view><view class="banner"><text>hello</text></view><view>{{i.cn[0].cn[0].v}}</view></view></template>"#
+  );
+  // 不指定策略时，带有 key 的节点会保留在 JSX 中
+  assert_eq!(
+    get_template(
+      &mut get_visitor(),
+      &input.replace(r#" compileMode="static""#, "")
+    ),
+    r#"<template name="tmpl_0_f0t0"><view><view class="banner"><text>hello</text></view><view>{{i.cn[1].cn[0].v}}</view></view></template>"#
+  );
+
+  let mut visitor = get_visitor();
+  assert_eq!(
+    collect_diagnostics(
+      &mut visitor,
+      r#"
+      function Index () {
+        return (
+          <View compileMode>
+            <View compileMode="static">{content}</View>
+          </View>
+        )
+      }
+      "#,
+    ),
+    vec![(
+      Level::Error,
+      String::from("compileMode=\"static\" 的子树中不能包含动态内容或自定义组件")
+    )]
+  );
+}
+
+#[test]
+fn should_support_swiper_item() {
+  let tmpl = get_template(
+    &mut get_visitor(),
+    r#"
+    import { SwiperItem } from '@tarojs/components'
+    function Index () {
+      return (
+        <View compileMode>
+          <SwiperItem className="slide" class="first" itemId={id}>
+            <Text>{title}</Text>
+          </SwiperItem>
+        </View>
+      )
+    }
+    "#,
+  );
+  assert_eq!(
+    tmpl,
+    r#"<template name="tmpl_0_f0t0"><view><swiper-item class="slide first" item-id="{{i.cn[0].p0}}"><text>{{i.cn[0].cn[0].cn[0].v}}</text></swiper-item></view></template>"#
+  );
+}
+
+#[test]
+fn should_transform_create_element_calls() {
+  let mut visitor = get_visitor();
+  visitor.config.transform_create_element = true;
+  let tmpl = get_template(
+    &mut visitor,
+    r#"
+    function Index () {
+      return React.createElement(
+        View,
+        { compileMode: true },
+        React.createElement(View, { className: cls }, content),
+        React.createElement(Text, null, "hello")
+      )
+    }
+    "#,
+  );
+  let expected = get_template(
+    &mut get_visitor(),
+    r#"
+    function Index () {
+      return (
+        <View compileMode>
+          <View className={cls}>{content}</View>
+          <Text>hello</Text>
+        </View>
+      )
+    }
+    "#,
+  );
+  assert_eq!(tmpl, expected);
+  assert_eq!(
+    tmpl,
+    r#"<template name="tmpl_0_f0t0"><view><view class="{{i.cn[0].cl}}">{{i.cn[0].cn[0].v}}</view><text>hello</text></view></template>"#
+  );
+}
+
+#[test]
+fn should_warn_and_drop_svg_elements() {
+  let mut visitor = get_visitor();
+  let diagnostics = collect_diagnostics(
+    &mut visitor,
+    r#"
+    function Index () {
+      return (
+        <View compileMode>
+          <svg viewBox="0 0 24 24">
+            <path d="M0 0h24v24H0z" />
+          </svg>
+          <View>{content}</View>
+        </View>
+      )
+    }
+    "#,
+  );
+  assert_eq!(
+    diagnostics,
+    vec![(
+      Level::Warning,
+      String::from("小程序模板不支持 SVG 元素，该节点会被忽略，请改用 Image 组件")
+    )]
+  );
+  assert_eq!(
+    visitor.templates.get("f0t0").unwrap(),
+    r#"<template name="tmpl_0_f0t0"><view><view>{{i.cn[0].cn[0].v}}</view></view></template>"#
+  );
+}
+
+#[test]
+fn should_keep_source_order_of_static_and_dynamic_children() {
+  // 静态节点从 JSX 中删除、动态节点保留，模板中的输出顺序仍需与源码一致
+  assert_eq!(
+    get_template(
+      &mut get_visitor(),
+      r#"
+      function Index () {
+        return (
+          <View compileMode>
+            <Text>first</Text>
+            <View>{a}</View>
+            <View>second</View>
+            {b}
+            <Image src={src} />
+            <Text>last</Text>
+          </View>
+        )
+      }
+      "#,
+    ),
+    r#"<template name="tmpl_0_f0t0"><view><text>first</text><view>{{i.cn[0].cn[0].v}}</view><view>second</view>{{i.cn[1].v}}<image src="{{i.cn[2].p3}}"/><text>last</text></view></template>"#
+  );
+}
+
+#[test]
+fn should_fall_back_for_unregistered_taro_component() {
+  let input = r#"
+    import { CoverView } from '@tarojs/components'
+
+    function Index () {
+      return (
+        <View compileMode>
+          <CoverView className="box">{content}</CoverView>
+        </View>
+      )
+    }
+    "#;
+
+  let mut visitor = get_visitor();
+  visitor.config.unregistered_component_fallback = Some(String::from("view"));
+  assert_eq!(
+    get_template(&mut visitor, input),
+    r#"<template name="tmpl_0_f0t0"><view><view class="box">{{i.cn[0].cn[0].v}}</view></view></template>"#
+  );
+
+  // 未配置时交由运行时渲染，并给出提示
+  assert_eq!(
+    collect_diagnostics(&mut get_visitor(), input),
+    vec![(
+      Level::Warning,
+      String::from("当前平台没有注册 cover-view 组件，将交由运行时渲染")
+    )]
+  );
+}
+
+#[test]
+fn should_warn_when_exceeding_max_depth() {
+  let input = r#"
+    function Index () {
+      return (
+        <View compileMode>
+          <View>
+            <View>
+              <Text>{a}</Text>
+            </View>
+          </View>
+          <View>{b}</View>
+        </View>
+      )
+    }
+    "#;
+  assert!(collect_diagnostics(&mut get_visitor(), input).is_empty());
+
+  let mut visitor = get_visitor();
+  visitor.config.max_depth = 2;
+  assert_eq!(
+    collect_diagnostics(&mut visitor, input),
+    vec![(
+      Level::Warning,
+      String::from("模板嵌套层级超过了配置的最大层级 2，部分平台可能无法正常渲染")
+    )]
+  );
+}
+
+#[test]
+fn should_flatten_suspense_boundary() {
+  let mut visitor = get_visitor();
+  let diagnostics = collect_diagnostics(
+    &mut visitor,
+    r#"
+    function Index () {
+      return (
+        <View compileMode>
+          <Suspense fallback={<Loading />}>
+            <View>{content}</View>
+          </Suspense>
+        </View>
+      )
+    }
+    "#,
+  );
+  assert_eq!(
+    diagnostics,
+    vec![(
+      Level::Warning,
+      String::from("Suspense 在模板中没有对应实现，已展开为其子节点，fallback 不会生效")
+    )]
+  );
+  assert_eq!(
+    visitor.templates.get("f0t0").unwrap(),
+    r#"<template name="tmpl_0_f0t0"><view><view>{{i.cn[0].cn[0].cn[0].v}}</view></view></template>"#
+  );
+}
+
+#[test]
+fn should_output_tags_in_configured_case() {
+  let mut visitor = get_visitor();
+  visitor.config.tag_case = TagCase::Pascal;
+  assert_eq!(
+    get_template(
+      &mut visitor,
+      r#"
+      function Index () {
+        return (
+          <View compileMode>
+            <Text>{a}</Text>
+            <Image src={src} />
+            <MovableView direction={direction} />
+          </View>
+        )
+      }
+      "#,
+    ),
+    r#"<template name="tmpl_0_f0t0"><View><Text>{{i.cn[0].cn[0].v}}</Text><Image src="{{i.cn[1].p3}}"/><MovableView direction="{{i.cn[2].p0}}"></MovableView></View></template>"#
+  );
+}
+
+#[test]
+fn should_report_spread_children() {
+  let diagnostics = collect_diagnostics(
+    &mut get_visitor(),
+    r#"
+    function Index () {
+      return (
+        <View compileMode>
+          <View>{...items}</View>
+        </View>
+      )
+    }
+    "#,
+  );
+  assert_eq!(
+    diagnostics,
+    vec![(
+      Level::Error,
+      String::from("子节点不支持展开语法 {...}，请改用 {list.map(...)} 渲染列表")
+    )]
+  );
+}
+
+#[test]
+fn should_warn_self_referential_render_fn() {
+  let diagnostics = collect_diagnostics(
+    &mut get_visitor(),
+    r#"
+    function renderNode (node) {
+      return (
+        <View compileMode>
+          <Text>{node.name}</Text>
+          <View>{renderNode(node.next)}</View>
+          <View>{renderFooter()}</View>
+        </View>
+      )
+    }
+    "#,
+  );
+  assert_eq!(
+    diagnostics,
+    vec![(
+      Level::Warning,
+      String::from("渲染函数 renderNode 递归调用了自身，不会在编译期展开，将交由运行时渲染")
+    )]
+  );
+}
@@ -1,5 +1,14 @@
-use super::{get_syntax_config, tr};
-use swc_core::ecma::transforms::testing::test;
+use super::{collect_diagnostics, get_syntax_config, get_template, get_visitor, parse_module, tr};
+use crate::transform::TransformVisitor;
+use std::collections::HashMap;
+use swc_core::{
+  common::errors::Level,
+  ecma::{
+    ast::{Ident, JSXAttr, JSXAttrName, JSXAttrValue, JSXExpr, JSXExprContainer},
+    transforms::testing::test,
+    visit::{Visit, VisitMutWith, VisitWith},
+  },
+};
 
 test!(
   get_syntax_config(),
@@ -73,3 +82,189 @@ test!(
   }
   "#
 );
+
+#[test]
+fn should_accept_else_following_if() {
+  let mut visitor = get_visitor();
+  let diagnostics = collect_diagnostics(
+    &mut visitor,
+    r#"
+    function Index () {
+      return (
+        <View compileMode>
+          <View compileIf={cond}>{a}</View>
+          {/* 注释不影响相邻关系 */}
+          <View compileElse>{b}</View>
+        </View>
+      )
+    }
+    "#,
+  );
+  assert!(diagnostics.is_empty());
+  assert_eq!(
+    visitor.templates.get("f0t0").unwrap(),
+    r#"<template name="tmpl_0_f0t0"><view><view wx:if="{{i.cn[0].compileIf}}">{{i.cn[0].cn[0].v}}</view><view wx:else>{{i.cn[1].cn[0].v}}</view></view></template>"#
+  );
+}
+
+#[test]
+fn should_report_orphaned_else() {
+  let diagnostics = collect_diagnostics(
+    &mut get_visitor(),
+    r#"
+    function Index () {
+      return (
+        <View compileMode>
+          <View compileIf={cond}>{a}</View>
+          <View>{b}</View>
+          <View compileElse>{c}</View>
+        </View>
+      )
+    }
+    "#,
+  );
+  assert_eq!(
+    diagnostics,
+    vec![(
+      Level::Error,
+      String::from("compileElse 必须紧跟在带有 compileIf 的兄弟节点之后")
+    )]
+  );
+}
+
+#[test]
+fn should_warn_consecutive_else() {
+  let diagnostics = collect_diagnostics(
+    &mut get_visitor(),
+    r#"
+    function Index () {
+      return (
+        <View compileMode>
+          <View compileIf={cond}>{a}</View>
+          <View compileElse>{b}</View>
+          <View compileElse>{c}</View>
+        </View>
+      )
+    }
+    "#,
+  );
+  assert_eq!(
+    diagnostics,
+    vec![(
+      Level::Warning,
+      String::from("连续出现多个 compileElse 节点，中间缺少带有 compileIf 的兄弟节点")
+    )]
+  );
+}
+
+#[test]
+fn should_skip_descendants_of_ignored_node() {
+  struct ClassCollector(Vec<bool>);
+  impl Visit for ClassCollector {
+    fn visit_jsx_attr(&mut self, attr: &JSXAttr) {
+      if let JSXAttrName::Ident(Ident { sym, .. }) = &attr.name {
+        if sym == "className" {
+          let is_array = matches!(
+            &attr.value,
+            Some(JSXAttrValue::JSXExprContainer(JSXExprContainer {
+              expr: JSXExpr::Expr(expr),
+              ..
+            })) if expr.is_array()
+          );
+          self.0.push(is_array);
+        }
+      }
+    }
+  }
+
+  let mut module = parse_module(
+    r#"
+    function Index () {
+      return (
+        <View compileMode>
+          <View compileIgnore>
+            <View className={['a', 'b']}>{content}</View>
+          </View>
+          <View className={['a', 'b']} />
+        </View>
+      )
+    }
+    "#,
+  );
+  let mut visitor = get_visitor();
+  module.visit_mut_with(&mut visitor);
+  assert_eq!(
+    visitor.templates.get("f0t0").unwrap(),
+    r#"<template name="tmpl_0_f0t0"><view><view class="a b"></view></view></template>"#
+  );
+
+  // 被忽略节点的后代保持原样，没有被预处理
+  let mut collector = ClassCollector(vec![]);
+  module.visit_with(&mut collector);
+  assert_eq!(collector.0, vec![true]);
+}
+
+#[test]
+fn should_support_inline_condition_text_in_text() {
+  let tmpl = get_template(
+    &mut get_visitor(),
+    r#"
+    function Index () {
+      return (
+        <View compileMode>
+          <Text>{cond && 'hello'}</Text>
+        </View>
+      )
+    }
+    "#,
+  );
+  assert_eq!(
+    tmpl,
+    r#"<template name="tmpl_0_f0t0"><view><text><text wx:if="{{i.cn[0].cn[0].compileIf}}">hello</text></text></view></template>"#
+  );
+}
+
+#[test]
+fn should_fall_back_to_platform_default_adapter() {
+  let mut config = get_visitor().config;
+  config.platform = String::from("ALIPAY");
+  config.adapter = HashMap::from([(String::from("else"), String::from("a:else"))]);
+  let tmpl = get_template(
+    &mut TransformVisitor::new(config),
+    r#"
+    function Index () {
+      return (
+        <View compileMode>
+          {cond ? <View>{a}</View> : <View>{b}</View>}
+        </View>
+      )
+    }
+    "#,
+  );
+  assert_eq!(
+    tmpl,
+    r#"<template name="tmpl_0_f0t0"><view><view a:if="{{i.cn[0].compileIf}}">{{i.cn[0].cn[0].v}}</view><view a:else>{{i.cn[0].cn[0].v}}</view></view></template>"#
+  );
+}
+
+#[test]
+fn should_compose_loop_in_conditional_consequent() {
+  let mut visitor = get_visitor();
+  let diagnostics = collect_diagnostics(
+    &mut visitor,
+    r#"
+    function Index () {
+      return (
+        <View compileMode>
+          {cond ? list.map(item => <View>{item.name}</View>) : <Text>empty</Text>}
+        </View>
+      )
+    }
+    "#,
+  );
+  assert!(diagnostics.is_empty());
+  assert_eq!(
+    visitor.templates.get("f0t0").unwrap(),
+    r#"<template name="tmpl_0_f0t0"><view><block wx:if="{{i.cn[0].compileIf}}"><view wx:for="{{i.cn[0].cn}}" wx:key="sid">{{item.cn[0].v}}</view></block><text wx:else>empty</text></view></template>"#
+  );
+}
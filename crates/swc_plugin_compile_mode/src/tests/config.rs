@@ -0,0 +1,109 @@
+use crate::{PluginConfig, TagCase, UnitPolicy};
+use std::collections::HashMap;
+
+#[test]
+fn should_build_config_with_defaults() {
+  let built = PluginConfig::builder().tmpl_prefix("f0").build();
+  let parsed = serde_json::from_str::<PluginConfig>(r#"{ "tmpl_prefix": "f0" }"#).unwrap();
+
+  assert_eq!(built.tmpl_prefix, parsed.tmpl_prefix);
+  assert_eq!(built.platform, parsed.platform);
+  assert_eq!(built.platform, "WEAPP");
+  assert_eq!(built.is_harmony, parsed.is_harmony);
+  assert_eq!(built.is_use_xs, parsed.is_use_xs);
+  assert!(built.is_use_xs);
+  assert_eq!(built.template_tag, parsed.template_tag);
+  assert_eq!(built.components, parsed.components);
+  assert_eq!(built.adapter, parsed.adapter);
+  assert_eq!(built.support_events, parsed.support_events);
+  assert_eq!(built.support_components, parsed.support_components);
+  assert_eq!(built.event_adapter, parsed.event_adapter);
+  assert!(built.component_replace.is_empty());
+  assert_eq!(
+    built.harmony_lazy_children_threshold,
+    parsed.harmony_lazy_children_threshold
+  );
+  assert_eq!(built.warn_loop_without_key, parsed.warn_loop_without_key);
+  assert_eq!(built.unit_policy, parsed.unit_policy);
+  assert_eq!(built.render_fn_prefixes, parsed.render_fn_prefixes);
+  assert_eq!(built.render_fn_prefixes, vec![String::from("render")]);
+  assert_eq!(built.inline_simple_exprs, parsed.inline_simple_exprs);
+  assert_eq!(
+    built.component_default_attrs,
+    parsed.component_default_attrs
+  );
+  assert_eq!(
+    built.transform_create_element,
+    parsed.transform_create_element
+  );
+  assert_eq!(
+    built.extra_scroll_view_props,
+    parsed.extra_scroll_view_props
+  );
+  assert_eq!(built.lowercase_events, parsed.lowercase_events);
+  assert_eq!(built.dynamic_id_attr, parsed.dynamic_id_attr);
+  assert_eq!(built.dynamic_id_attr, "_dynamicID");
+  assert_eq!(
+    built.unregistered_component_fallback,
+    parsed.unregistered_component_fallback
+  );
+  assert!(built.unregistered_component_fallback.is_none());
+  assert_eq!(built.max_depth, parsed.max_depth);
+  assert_eq!(built.hoist_loop_templates, parsed.hoist_loop_templates);
+  assert_eq!(
+    built.warn_loop_inline_objects,
+    parsed.warn_loop_inline_objects
+  );
+  assert_eq!(built.tag_case, parsed.tag_case);
+  assert_eq!(built.tag_case, TagCase::Lower);
+  assert_eq!(built.minify, parsed.minify);
+  assert!(!built.minify);
+  assert_eq!(built.key_as_loop_key, parsed.key_as_loop_key);
+  assert!(!built.key_as_loop_key);
+}
+
+#[test]
+fn should_build_config_with_custom_fields() {
+  let config = PluginConfig::builder()
+    .tmpl_prefix("f1")
+    .platform("ALIPAY")
+    .is_use_xs(false)
+    .adapter(HashMap::from([(String::from("if"), String::from("a:if"))]))
+    .unit_policy(UnitPolicy::PxToRpx {
+      design_width: 375.0,
+    })
+    .build();
+
+  assert_eq!(config.tmpl_prefix, "f1");
+  assert_eq!(config.platform, "ALIPAY");
+  assert!(!config.is_use_xs);
+  assert_eq!(config.adapter.get("if").unwrap(), "a:if");
+  assert_eq!(
+    config.unit_policy,
+    UnitPolicy::PxToRpx {
+      design_width: 375.0
+    }
+  );
+}
+
+#[test]
+fn should_reject_non_positive_design_width() {
+  let parsed = serde_json::from_str::<PluginConfig>(
+    r#"{ "tmpl_prefix": "f0", "unit_policy": { "type": "px_to_rpx", "design_width": 375 } }"#,
+  )
+  .unwrap();
+  assert_eq!(
+    parsed.unit_policy,
+    UnitPolicy::PxToRpx {
+      design_width: 375.0
+    }
+  );
+
+  let error = serde_json::from_str::<PluginConfig>(
+    r#"{ "tmpl_prefix": "f0", "unit_policy": { "type": "px_to_rpx", "design_width": 0 } }"#,
+  )
+  .unwrap_err();
+  assert!(error
+    .to_string()
+    .contains("unit_policy 的 design_width 必须大于 0"));
+}
@@ -1,5 +1,8 @@
-use super::{get_syntax_config, tr};
-use swc_core::ecma::transforms::testing::test;
+use super::{collect_diagnostics, get_syntax_config, get_visitor, parse_module, tr};
+use swc_core::{
+  common::errors::Level,
+  ecma::{transforms::testing::test, visit::VisitMutWith},
+};
 
 test!(
   get_syntax_config(),
@@ -18,3 +21,90 @@ test!(
       }
     "#
 );
+
+#[test]
+fn should_write_templates_to_sink() {
+  let mut visitor = get_visitor();
+  let mut module = parse_module(
+    r#"
+    function Index () {
+      return (
+        <View>
+          <View compileMode>{a}</View>
+          <View compileMode><Text>{b}</Text></View>
+        </View>
+      )
+    }
+    "#,
+  );
+  module.visit_mut_with(&mut visitor);
+
+  let mut sink = String::new();
+  visitor.write_templates(&mut sink).unwrap();
+  let batch = [
+    visitor.templates.get("f0t0").unwrap().as_str(),
+    visitor.templates.get("f0t1").unwrap().as_str(),
+  ]
+  .concat();
+  assert_eq!(sink, batch);
+  assert_eq!(
+    sink,
+    r#"<template name="tmpl_0_f0t0"><view>{{i.cn[0].v}}</view></template><template name="tmpl_0_f0t1"><view><text>{{i.cn[0].cn[0].v}}</text></view></template>"#
+  );
+}
+
+#[test]
+fn should_compile_memo_and_forward_ref_components() {
+  let mut visitor = get_visitor();
+  let mut module = parse_module(
+    r#"
+    const Card = React.memo(function Card ({ title }) {
+      return <View compileMode><Text>{title}</Text></View>
+    })
+    const Field = forwardRef((props, ref) => (
+      <View compileMode><Input ref={ref} value={props.value} /></View>
+    ))
+    function Index () {
+      return <View compileMode><Card title={a} /></View>
+    }
+    "#,
+  );
+  module.visit_mut_with(&mut visitor);
+
+  // 包裹在 React.memo、forwardRef 中的组件同样会被编译
+  assert_eq!(
+    visitor.templates.get("f0t0").unwrap(),
+    r#"<template name="tmpl_0_f0t0"><view><text>{{i.cn[0].cn[0].v}}</text></view></template>"#
+  );
+  assert_eq!(
+    visitor.templates.get("f0t1").unwrap(),
+    r#"<template name="tmpl_0_f0t1"><view><input value="{{i.cn[0].value}}"/></view></template>"#
+  );
+  // 使用处与普通的自定义组件一样交由运行时渲染
+  assert_eq!(
+    visitor.templates.get("f0t2").unwrap(),
+    r#"<template name="tmpl_0_f0t2"><view><template is="{{xs.a(c, i.cn[0].nn, l)}}" data="{{i:i.cn[0],c:c+1,l:xs.f(l,i.cn[0].nn)}}" /></view></template>"#
+  );
+}
+
+#[test]
+fn should_unwrap_memo_wrapped_render_fn() {
+  // memo 包裹的渲染函数同样按函数名识别，递归调用自身时给出提示
+  let diagnostics = collect_diagnostics(
+    &mut get_visitor(),
+    r#"
+    const renderTree = React.memo(forwardRef((node) => (
+      <View compileMode>
+        <View>{renderTree(node.child)}</View>
+      </View>
+    )))
+    "#,
+  );
+  assert_eq!(
+    diagnostics,
+    vec![(
+      Level::Warning,
+      String::from("渲染函数 renderTree 递归调用了自身，不会在编译期展开，将交由运行时渲染")
+    )]
+  );
+}
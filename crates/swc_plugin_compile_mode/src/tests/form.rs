@@ -0,0 +1,144 @@
+use super::{collect_diagnostics, get_template, get_visitor};
+use swc_core::common::errors::Level;
+
+#[test]
+fn should_bind_value_of_controlled_input() {
+  let tmpl = get_template(
+    &mut get_visitor(),
+    r#"
+    function Index () {
+      return (
+        <View compileMode>
+          <Input value={value} onInput={handleInput} />
+        </View>
+      )
+    }
+    "#,
+  );
+  assert_eq!(
+    tmpl,
+    r#"<template name="tmpl_0_f0t0"><view><input bindinput="eh" data-sid="{{i.cn[0].sid}}" id="{{i.cn[0].sid}}" value="{{i.cn[0].value}}"/></view></template>"#
+  );
+}
+
+#[test]
+fn should_treat_on_change_of_textarea_as_input() {
+  let tmpl = get_template(
+    &mut get_visitor(),
+    r#"
+    function Index () {
+      return (
+        <View compileMode>
+          <Textarea value={value} onChange={handleChange} />
+        </View>
+      )
+    }
+    "#,
+  );
+  assert_eq!(
+    tmpl,
+    r#"<template name="tmpl_0_f0t0"><view><textarea bindinput="eh" data-sid="{{i.cn[0].sid}}" id="{{i.cn[0].sid}}" value="{{i.cn[0].value}}"></textarea></view></template>"#
+  );
+}
+
+#[test]
+fn should_output_default_value_of_uncontrolled_input() {
+  let tmpl = get_template(
+    &mut get_visitor(),
+    r#"
+    function Index () {
+      return (
+        <View compileMode>
+          <Input defaultValue="hello" onChange={handleChange} />
+        </View>
+      )
+    }
+    "#,
+  );
+  assert_eq!(
+    tmpl,
+    r#"<template name="tmpl_0_f0t0"><view><input bindinput="eh" data-sid="{{i.cn[0].sid}}" id="{{i.cn[0].sid}}" value="hello"/></view></template>"#
+  );
+}
+
+#[test]
+fn should_keep_selector_picker_props() {
+  let mut visitor = get_visitor();
+  let input = r#"
+    import { Picker } from '@tarojs/components'
+
+    function Index () {
+      return (
+        <View compileMode>
+          <Picker mode="selector" range={list} value={index} start="09:00" headerText="选择" data-id="picker" onTap={onTap} onChange={onChange}>
+            <View>{label}</View>
+          </Picker>
+        </View>
+      )
+    }
+    "#;
+  assert_eq!(
+    collect_diagnostics(&mut visitor, input),
+    vec![(
+      Level::Warning,
+      String::from("Picker mode=\"selector\" 不支持 start 属性，已忽略")
+    )]
+  );
+  assert_eq!(
+    visitor.templates.get("f0t0").unwrap(),
+    r#"<template name="tmpl_0_f0t0"><view><picker bindchange="eh" bindtap="eh" data-id="picker" data-sid="{{i.cn[0].sid}}" header-text="选择" id="{{i.cn[0].sid}}" mode="selector" range="{{i.cn[0].p2}}" value="{{i.cn[0].value}}"><view>{{i.cn[0].cn[0].cn[0].v}}</view></picker></view></template>"#
+  );
+}
+
+#[test]
+fn should_keep_date_picker_props() {
+  let mut visitor = get_visitor();
+  let input = r#"
+    import { Picker } from '@tarojs/components'
+
+    function Index () {
+      return (
+        <View compileMode>
+          <Picker mode="date" value={date} start="2020-01-01" range={list} onChange={onChange} onColumnChange={onColumnChange}>
+            <View>{date}</View>
+          </Picker>
+        </View>
+      )
+    }
+    "#;
+  assert_eq!(
+    collect_diagnostics(&mut visitor, input),
+    vec![(
+      Level::Warning,
+      String::from("Picker mode=\"date\" 不支持 range 属性，已忽略")
+    )]
+  );
+  assert_eq!(
+    visitor.templates.get("f0t0").unwrap(),
+    r#"<template name="tmpl_0_f0t0"><view><picker bindchange="eh" bindcolumnchange="eh" data-sid="{{i.cn[0].sid}}" id="{{i.cn[0].sid}}" mode="date" start="2020-01-01" value="{{i.cn[0].value}}"><view>{{i.cn[0].cn[0].cn[0].v}}</view></picker></view></template>"#
+  );
+}
+
+#[test]
+fn should_bind_focus_and_blur_of_form_controls() {
+  let input = r#"
+    function Index () {
+      return (
+        <View compileMode>
+          <Input onFocus={handleFocus} onBlur={handleBlur} />
+        </View>
+      )
+    }
+    "#;
+  assert_eq!(
+    get_template(&mut get_visitor(), input),
+    r#"<template name="tmpl_0_f0t0"><view><input bindblur="eh" bindfocus="eh" data-sid="{{i.cn[0].sid}}" id="{{i.cn[0].sid}}"/></view></template>"#
+  );
+
+  let mut visitor = get_visitor();
+  visitor.config.platform = String::from("ALIPAY");
+  assert_eq!(
+    get_template(&mut visitor, input),
+    r#"<template name="tmpl_0_f0t0"><view><input data-sid="{{i.cn[0].sid}}" id="{{i.cn[0].sid}}" onBlur="eh" onFocus="eh"/></view></template>"#
+  );
+}
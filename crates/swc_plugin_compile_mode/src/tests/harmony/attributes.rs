@@ -1,5 +1,9 @@
-use super::{get_syntax_config, tr};
-use swc_core::ecma::transforms::testing::test;
+use super::{get_harmony_visitor, get_syntax_config, parse_module, tr};
+use swc_core::ecma::{
+  ast::*,
+  transforms::testing::test,
+  visit::{Visit, VisitMutWith, VisitWith},
+};
 
 test!(
   get_syntax_config(),
@@ -43,3 +47,93 @@ test!(
       }
     "#
 );
+
+#[test]
+fn should_use_configured_dynamic_id_attr() {
+  struct AttrCollector(Vec<String>);
+  impl Visit for AttrCollector {
+    fn visit_jsx_attr(&mut self, attr: &JSXAttr) {
+      if let JSXAttrName::Ident(Ident { sym, .. }) = &attr.name {
+        self.0.push(sym.to_string());
+      }
+    }
+  }
+
+  let mut visitor = get_harmony_visitor();
+  visitor.config.dynamic_id_attr = String::from("_ti");
+  let mut module = parse_module(
+    r#"
+    function Index () {
+      return (
+        <View compileMode>
+          <View style={style}>{content}</View>
+        </View>
+      )
+    }
+    "#,
+  );
+  module.visit_mut_with(&mut visitor);
+
+  let mut collector = AttrCollector(vec![]);
+  module.visit_with(&mut collector);
+  assert_eq!(collector.0.iter().filter(|name| *name == "_ti").count(), 2);
+  assert!(!collector.0.contains(&String::from("_dynamicID")));
+}
+
+#[test]
+fn should_not_assign_dynamic_id_to_static_subtree() {
+  // (className, 是否带有动态 id)
+  struct DynamicIdCollector(Vec<(String, bool)>);
+  impl Visit for DynamicIdCollector {
+    fn visit_jsx_element(&mut self, el: &JSXElement) {
+      let mut class_name = String::new();
+      let mut has_dynamic_id = false;
+      for attr in &el.opening.attrs {
+        if let JSXAttrOrSpread::JSXAttr(JSXAttr {
+          name: JSXAttrName::Ident(Ident { sym, .. }),
+          value,
+          ..
+        }) = attr
+        {
+          if sym == "_dynamicID" {
+            has_dynamic_id = true;
+          } else if sym == "className" {
+            if let Some(JSXAttrValue::Lit(Lit::Str(Str { value, .. }))) = value {
+              class_name = value.to_string();
+            }
+          }
+        }
+      }
+      if !class_name.is_empty() {
+        self.0.push((class_name, has_dynamic_id));
+      }
+      el.visit_children_with(self);
+    }
+  }
+
+  let mut visitor = get_harmony_visitor();
+  let mut module = parse_module(
+    r#"
+    function Index () {
+      return (
+        <View compileMode>
+          <View className="static"><Text className="static-text">hello</Text></View>
+          <View className="dynamic" style={style}>{content}</View>
+        </View>
+      )
+    }
+    "#,
+  );
+  module.visit_mut_with(&mut visitor);
+
+  let mut collector = DynamicIdCollector(vec![]);
+  module.visit_with(&mut collector);
+  assert_eq!(
+    collector.0,
+    vec![
+      (String::from("static"), false),
+      (String::from("static-text"), false),
+      (String::from("dynamic"), true),
+    ]
+  );
+}
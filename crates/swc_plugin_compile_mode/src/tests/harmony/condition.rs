@@ -1,5 +1,5 @@
-use super::{get_syntax_config, tr};
-use swc_core::ecma::transforms::testing::test;
+use super::{get_harmony_visitor, get_syntax_config, parse_module, tr};
+use swc_core::ecma::{transforms::testing::test, visit::VisitMutWith};
 
 test!(
   get_syntax_config(),
@@ -77,3 +77,31 @@ test!(
   }
   "#
 );
+
+#[test]
+fn should_support_conditional_text() {
+  let mut visitor = get_harmony_visitor();
+  let mut module = parse_module(
+    r#"
+    function Index () {
+      return (
+        <View compileMode>
+          {show ? name : 'guest'}
+          {show ? title : <View />}
+        </View>
+      )
+    }
+    "#,
+  );
+  module.visit_mut_with(&mut visitor);
+
+  let tmpl = visitor.templates.get("f0t0").unwrap();
+  // 两个分支都是文本时只输出一次文本节点，不从文本节点上读取 compileIf
+  assert!(tmpl.contains("\n      createText(this.node0.childNodes[0] as TaroTextElement)\n"));
+  assert!(!tmpl.contains("(this.node0.childNodes[0] as TaroElement)._attrs.compileIf"));
+  // 文本分支与元素分支混用时，由元素分支承载 compileIf
+  assert!(tmpl.contains("if ((this.node0.childNodes[1] as TaroElement)._attrs.compileIf) {"));
+  assert!(tmpl.contains(
+    "} else {\n        createText(this.node0.childNodes[1] as TaroTextElement)\n      }"
+  ));
+}
@@ -1,5 +1,5 @@
-use super::{get_syntax_config, tr};
-use swc_core::ecma::transforms::testing::test;
+use super::{get_harmony_visitor, get_syntax_config, parse_module, tr};
+use swc_core::ecma::{transforms::testing::test, visit::VisitMutWith};
 
 test!(
   get_syntax_config(),
@@ -65,3 +65,36 @@ test!(
   }
   "#
 );
+
+#[test]
+fn should_minify_template_when_enabled() {
+  let input = r#"
+    function Index () {
+      return (
+        <View compileMode>
+          <View>{a}</View>
+          <Text>hello</Text>
+        </View>
+      )
+    }
+    "#;
+  let mut pretty_visitor = get_harmony_visitor();
+  parse_module(input).visit_mut_with(&mut pretty_visitor);
+  let pretty = pretty_visitor.templates.get("f0t0").unwrap();
+
+  let mut minified_visitor = get_harmony_visitor();
+  minified_visitor.config.minify = true;
+  parse_module(input).visit_mut_with(&mut minified_visitor);
+  let minified = minified_visitor.templates.get("f0t0").unwrap();
+
+  assert!(pretty.contains("\n  "));
+  assert!(!minified.contains("\n "));
+  assert!(!minified.contains("\n\n"));
+  assert!(minified.len() < pretty.len());
+  let pretty_lines: Vec<&str> = pretty
+    .lines()
+    .map(|line| line.trim())
+    .filter(|line| !line.is_empty())
+    .collect();
+  assert_eq!(minified.lines().collect::<Vec<&str>>(), pretty_lines);
+}
@@ -0,0 +1,68 @@
+use super::{get_harmony_visitor, parse_module};
+use swc_core::ecma::visit::VisitMutWith;
+
+const LAZY_ROOT_CHILDREN: &str = "createLazyChildren(this.node0)";
+
+fn transform(visitor: &mut crate::transform_harmony::TransformVisitor, input: &str) -> String {
+  let mut module = parse_module(input);
+  module.visit_mut_with(visitor);
+  visitor.templates.get("f0t0").unwrap().clone()
+}
+
+#[test]
+fn should_render_looping_children_lazily() {
+  let tmpl = transform(
+    &mut get_harmony_visitor(),
+    r#"
+    function Index () {
+      return (
+        <View compileMode>
+          <Text>title</Text>
+          {list.map(item => <View>{item.name}</View>)}
+        </View>
+      )
+    }
+    "#,
+  );
+  assert!(tmpl.contains(LAZY_ROOT_CHILDREN));
+}
+
+#[test]
+fn should_render_plain_children_eagerly() {
+  let tmpl = transform(
+    &mut get_harmony_visitor(),
+    r#"
+    function Index () {
+      return (
+        <View compileMode>
+          <View>{a}</View>
+          <View>{b}</View>
+          <View>{c}</View>
+        </View>
+      )
+    }
+    "#,
+  );
+  assert!(!tmpl.contains(LAZY_ROOT_CHILDREN));
+}
+
+#[test]
+fn should_render_large_children_lazily_by_threshold() {
+  let mut visitor = get_harmony_visitor();
+  visitor.config.harmony_lazy_children_threshold = 2;
+  let tmpl = transform(
+    &mut visitor,
+    r#"
+    function Index () {
+      return (
+        <View compileMode>
+          <View>{a}</View>
+          <View>{b}</View>
+          <View>{c}</View>
+        </View>
+      )
+    }
+    "#,
+  );
+  assert!(tmpl.contains(LAZY_ROOT_CHILDREN));
+}
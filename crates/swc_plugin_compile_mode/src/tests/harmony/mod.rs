@@ -1,4 +1,4 @@
-pub use super::get_syntax_config;
+pub use super::{get_syntax_config, parse_module};
 use crate::{transform_harmony::*, PluginConfig};
 use swc_core::ecma::visit::{as_folder, Fold, VisitMut};
 
@@ -6,9 +6,16 @@ mod attributes;
 mod children;
 mod condition;
 mod entry;
+mod lazy;
 mod looping;
+mod node_path;
+mod style;
 
 pub fn tr() -> impl Fold + VisitMut {
+  as_folder(get_harmony_visitor())
+}
+
+pub fn get_harmony_visitor() -> TransformVisitor {
   let config = serde_json::from_str::<PluginConfig>(
     r#"
       {
@@ -37,6 +44,5 @@ pub fn tr() -> impl Fold + VisitMut {
       }"#,
   )
   .unwrap();
-  let visitor = TransformVisitor::new(config);
-  as_folder(visitor)
+  TransformVisitor::new(config)
 }
@@ -0,0 +1,79 @@
+use super::{get_harmony_visitor, parse_module};
+use std::collections::HashMap;
+use swc_core::ecma::visit::VisitMutWith;
+
+#[test]
+fn should_render_current_node_path() {
+  let mut visitor = get_harmony_visitor();
+  visitor.node_name.push(String::from("node0"));
+  assert_eq!("node0", visitor.get_current_node_path());
+
+  visitor.push_node_stack(2);
+  visitor.push_node_stack(0);
+  let path = visitor.current_node_path();
+  assert_eq!(&[2, 0], path.indexes());
+  assert_eq!(
+    "node0.childNodes[2].childNodes[0]",
+    visitor.get_current_node_path()
+  );
+  assert_eq!(
+    "this.node0.childNodes[2].childNodes[0]",
+    visitor.get_dynamic_node_name(path)
+  );
+
+  visitor.pop_node_stack();
+  assert_eq!("node0.childNodes[2]", visitor.get_current_node_path());
+
+  visitor.deal_loop_now = true;
+  visitor.node_name.push(String::from("item"));
+  assert_eq!(
+    "item",
+    visitor.get_dynamic_node_name(visitor.current_node_path())
+  );
+}
+
+#[test]
+#[allow(deprecated)]
+fn should_keep_misspelled_dynamic_node_name_alias() {
+  let mut visitor = get_harmony_visitor();
+  visitor.node_name.push(String::from("node1"));
+  visitor.push_node_stack(3);
+
+  let path = visitor.current_node_path();
+  assert_eq!(
+    visitor.get_dynamic_node_name(path.clone()),
+    visitor.get_dynmaic_node_name(path)
+  );
+}
+
+#[test]
+fn should_collect_node_path_map() {
+  let mut visitor = get_harmony_visitor();
+  let mut module = parse_module(
+    r#"
+    function Index () {
+      return (
+        <View compileMode>
+          <Text>title</Text>
+          <View style={style}>
+            <Image src={src} />
+          </View>
+        </View>
+      )
+    }
+    "#,
+  );
+  module.visit_mut_with(&mut visitor);
+  assert_eq!(
+    visitor.get_node_path_map("f0t0"),
+    HashMap::from([
+      (String::from("node0"), String::from("node0")),
+      (String::from("node1"), String::from("node0.childNodes[1]")),
+      (
+        String::from("node2"),
+        String::from("node0.childNodes[1].childNodes[0]")
+      ),
+    ])
+  );
+  assert!(visitor.get_node_path_map("f0t1").is_empty());
+}
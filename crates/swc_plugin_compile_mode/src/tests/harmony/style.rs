@@ -0,0 +1,105 @@
+use super::get_harmony_visitor;
+use crate::{
+  utils::{
+    constants::*, get_harmony_component_style, get_harmony_replace_component_dependency_define,
+  },
+  ComponentReplace,
+};
+
+#[test]
+fn should_emit_image_style_when_image_is_used() {
+  let mut visitor = get_harmony_visitor();
+  visitor.component_set.insert(IMAGE_TAG.to_string());
+
+  let style = get_harmony_component_style(&mut visitor);
+  assert!(style.contains(HARMONY_IMAGE_STYLE_BIND));
+  assert!(!style.contains(HARMONY_TEXT_BUILDER));
+}
+
+#[test]
+fn should_not_emit_image_style_when_image_is_replaced() {
+  let mut visitor = get_harmony_visitor();
+  visitor.component_set.insert(IMAGE_TAG.to_string());
+  visitor.config.component_replace.insert(
+    IMAGE_TAG.to_string(),
+    ComponentReplace {
+      current_init: String::from("CustomImage({ node: node })"),
+      dependency_define: String::from("import { CustomImage } from './image'"),
+      builder_define: String::new(),
+    },
+  );
+
+  let style = get_harmony_component_style(&mut visitor);
+  assert!(!style.contains(HARMONY_IMAGE_STYLE_BIND));
+}
+
+#[test]
+fn should_emit_custom_builder_when_replaced_component_is_used() {
+  let builder = "@Builder\nfunction createCustomVideo (node: TaroElement) {\n  Video({ src: node.getAttribute('src') })\n}";
+  let mut visitor = get_harmony_visitor();
+  visitor.config.component_replace.insert(
+    String::from("video"),
+    ComponentReplace {
+      current_init: String::from("createCustomVideo(node)"),
+      dependency_define: String::new(),
+      builder_define: String::from(builder),
+    },
+  );
+
+  let style = get_harmony_component_style(&mut visitor);
+  assert!(!style.contains(builder));
+
+  visitor.component_set.insert(String::from("video"));
+  let style = get_harmony_component_style(&mut visitor);
+  assert!(style.contains(builder));
+}
+
+#[test]
+fn should_dedupe_shared_dependency_define() {
+  let define = "import { createCustomComponent } from './custom'";
+  let mut visitor = get_harmony_visitor();
+  for name in ["video", "map"] {
+    visitor.component_set.insert(String::from(name));
+    visitor.config.component_replace.insert(
+      String::from(name),
+      ComponentReplace {
+        current_init: String::from("createCustomComponent(node)"),
+        dependency_define: String::from(define),
+        builder_define: String::new(),
+      },
+    );
+  }
+
+  let dependency = get_harmony_replace_component_dependency_define(&mut visitor);
+  assert_eq!(dependency.matches(define).count(), 1);
+}
+
+#[test]
+fn should_emit_harmony_styles_in_stable_order() {
+  let emit = || {
+    let mut visitor = get_harmony_visitor();
+    visitor.component_set.insert(TEXT_TAG.to_string());
+    visitor.component_set.insert(IMAGE_TAG.to_string());
+    for name in ["video", "map", "canvas", "swiper", "picker"] {
+      visitor.component_set.insert(String::from(name));
+      visitor.config.component_replace.insert(
+        String::from(name),
+        ComponentReplace {
+          current_init: format!("{}(node)", name),
+          dependency_define: format!("import {{ {} }} from './{}'", name, name),
+          builder_define: format!("// builder of {}", name),
+        },
+      );
+    }
+    format!(
+      "{}{}",
+      get_harmony_replace_component_dependency_define(&mut visitor),
+      get_harmony_component_style(&mut visitor)
+    )
+  };
+
+  let first = emit();
+  for _ in 0..5 {
+    assert_eq!(first, emit());
+  }
+}
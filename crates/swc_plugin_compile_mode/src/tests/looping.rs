@@ -1,5 +1,12 @@
-use super::{get_syntax_config, tr};
-use swc_core::ecma::transforms::testing::test;
+use super::{collect_diagnostics, get_syntax_config, get_template, get_visitor, parse_module, tr};
+use swc_core::{
+  common::errors::Level,
+  ecma::{
+    ast::{Ident, JSXAttr, JSXAttrName},
+    transforms::testing::test,
+    visit::{Visit, VisitMutWith, VisitWith},
+  },
+};
 
 test!(
   get_syntax_config(),
@@ -131,3 +138,630 @@ test!(
     }
     "#
 );
+
+#[test]
+fn should_support_field_name_as_loop_key() {
+  let tmpl = get_template(
+    &mut get_visitor(),
+    r#"
+    function Index () {
+      return (
+        <View compileMode>
+          {list.map(item => <View compileForKey="id">{item.name}</View>)}
+        </View>
+      )
+    }
+    "#,
+  );
+  assert_eq!(
+    tmpl,
+    r#"<template name="tmpl_0_f0t0"><view><view wx:for="{{i.cn}}" wx:key="id">{{item.cn[0].v}}</view></view></template>"#
+  );
+}
+
+#[test]
+fn should_support_expression_as_loop_key() {
+  let tmpl = get_template(
+    &mut get_visitor(),
+    r#"
+    function Index () {
+      return (
+        <View compileMode>
+          {list.map(item => <View compileForKey="item.uid">{item.name}</View>)}
+        </View>
+      )
+    }
+    "#,
+  );
+  assert_eq!(
+    tmpl,
+    r#"<template name="tmpl_0_f0t0"><view><view wx:for="{{i.cn}}" wx:key="{{item.uid}}">{{item.cn[0].v}}</view></view></template>"#
+  );
+}
+
+#[test]
+fn should_loop_over_object_keys_and_entries() {
+  let tmpl = get_template(
+    &mut get_visitor(),
+    r#"
+    function Index () {
+      return (
+        <View compileMode>
+          <View>
+            {Object.keys(obj).map(k => <View key={k}>{k}</View>)}
+          </View>
+          <View>
+            {Object.entries(obj).map(([k, v]) => <View key={k}>{v.name}</View>)}
+          </View>
+        </View>
+      )
+    }
+    "#,
+  );
+  assert_eq!(
+    tmpl,
+    r#"<template name="tmpl_0_f0t0"><view><view><view wx:for="{{i.cn[0].cn}}" wx:key="sid">{{item.cn[0].v}}</view></view><view><view wx:for="{{i.cn[1].cn}}" wx:key="sid">{{item.cn[0].v}}</view></view></view></template>"#
+  );
+}
+
+#[test]
+fn should_use_configured_block_tag() {
+  let mut visitor = get_visitor();
+  visitor
+    .config
+    .adapter
+    .insert(String::from("block"), String::from("fragment"));
+  let tmpl = get_template(
+    &mut visitor,
+    r#"
+    function Index () {
+      return (
+        <View compileMode>
+          {list.map(item => (
+            <>
+              <View>{item.title}</View>
+              <View>{item.content}</View>
+            </>
+          ))}
+        </View>
+      )
+    }
+    "#,
+  );
+  assert_eq!(
+    tmpl,
+    r#"<template name="tmpl_0_f0t0"><view><fragment wx:for="{{i.cn}}" wx:key="sid"><view>{{item.cn[0].cn[0].v}}</view><view>{{item.cn[1].cn[0].v}}</view></fragment></view></template>"#
+  );
+}
+
+#[test]
+fn should_resolve_bindings_against_renamed_item() {
+  let tmpl = get_template(
+    &mut get_visitor(),
+    r#"
+    function Index () {
+      return (
+        <View compileMode>
+          <View>
+            {list.map(row => <View compileForItem="row" hoverClass={row.cls}>{row.name}</View>)}
+          </View>
+          <View>
+            {list.map(row => (
+              <View compileForItem="row">
+                {row.cells.map(cell => <Text>{cell}</Text>)}
+              </View>
+            ))}
+          </View>
+        </View>
+      )
+    }
+    "#,
+  );
+  assert_eq!(
+    tmpl,
+    r#"<template name="tmpl_0_f0t0"><view><view><view hover-class="{{xs.b(row.p1,'none')}}" wx:for="{{i.cn[0].cn}}" wx:for-item="row" wx:key="sid">{{row.cn[0].v}}</view></view><view><view wx:for="{{i.cn[1].cn}}" wx:for-item="row" wx:key="sid"><text wx:for="{{row.cn}}" wx:key="sid">{{item.cn[0].v}}</text></view></view></view></template>"#
+  );
+}
+
+fn collect_loop_diagnostics(warn_loop_without_key: bool, input: &str) -> Vec<(Level, String)> {
+  let mut visitor = get_visitor();
+  visitor.config.warn_loop_without_key = warn_loop_without_key;
+  collect_diagnostics(&mut visitor, input)
+}
+
+#[test]
+fn should_warn_when_loop_falls_back_to_default_key() {
+  let input = r#"
+    function Index () {
+      return (
+        <View compileMode>
+          {list.map(item => <View>{item.name}</View>)}
+        </View>
+      )
+    }
+    "#;
+  assert_eq!(
+    collect_loop_diagnostics(true, input),
+    vec![(
+      Level::Warning,
+      String::from("循环没有指定 compileForKey，将使用默认的 sid 作为 key")
+    )]
+  );
+  assert!(collect_loop_diagnostics(false, input).is_empty());
+}
+
+#[test]
+fn should_not_warn_when_loop_has_key() {
+  let diagnostics = collect_loop_diagnostics(
+    true,
+    r#"
+    function Index () {
+      return (
+        <View compileMode>
+          {list.map(item => <View compileForKey="id">{item.name}</View>)}
+          {list.map(item => <View compileForKey="sid">{item.name}</View>)}
+        </View>
+      )
+    }
+    "#,
+  );
+  assert!(diagnostics.is_empty());
+}
+
+#[test]
+fn should_put_if_and_for_on_the_same_node() {
+  let tmpl = get_template(
+    &mut get_visitor(),
+    r#"
+    function Index () {
+      return (
+        <View compileMode>
+          {show && list.map(item => <View>{item.name}</View>)}
+        </View>
+      )
+    }
+    "#,
+  );
+  assert_eq!(
+    tmpl,
+    r#"<template name="tmpl_0_f0t0"><view><view wx:for="{{i.cn[0].cn}}" wx:if="{{item.compileIf}}" wx:key="sid">{{item.cn[0].v}}</view></view></template>"#
+  );
+
+  let tmpl = get_template(
+    &mut get_visitor(),
+    r#"
+    function Index () {
+      return (
+        <View compileMode>
+          {show && list.map(item => (
+            <>
+              <Text>{item.name}</Text>
+              <Text>desc</Text>
+            </>
+          ))}
+        </View>
+      )
+    }
+    "#,
+  );
+  assert_eq!(
+    tmpl,
+    r#"<template name="tmpl_0_f0t0"><view><block wx:for="{{i.cn[0].cn}}" wx:if="{{item.compileIf}}" wx:key="sid"><text>{{item.cn[0].cn[0].v}}</text><text>desc</text></block></view></template>"#
+  );
+}
+
+#[test]
+fn should_keep_if_outside_loop_when_condition_uses_loop_param() {
+  let tmpl = get_template(
+    &mut get_visitor(),
+    r#"
+    function Index () {
+      return (
+        <View compileMode>
+          {item && list.map(item => <View>{item.name}</View>)}
+        </View>
+      )
+    }
+    "#,
+  );
+  assert_eq!(
+    tmpl,
+    r#"<template name="tmpl_0_f0t0"><view><block wx:if="{{i.cn[0].compileIf}}"><view wx:for="{{i.cn[0].cn}}" wx:key="sid">{{item.cn[0].v}}</view></block></view></template>"#
+  );
+}
+
+#[test]
+fn should_emit_custom_loop_index_name() {
+  let tmpl = get_template(
+    &mut get_visitor(),
+    r#"
+    function Index () {
+      return (
+        <View compileMode>
+          {list.map((item, idx) => <View>{idx}</View>)}
+        </View>
+      )
+    }
+    "#,
+  );
+  assert_eq!(
+    tmpl,
+    r#"<template name="tmpl_0_f0t0"><view><view wx:for="{{i.cn}}" wx:for-index="idx" wx:key="sid">{{item.cn[0].v}}</view></view></template>"#
+  );
+
+  let tmpl = get_template(
+    &mut get_visitor(),
+    r#"
+    function Index () {
+      return (
+        <View compileMode>
+          {list.map(function (item, index) {
+            return <View>{index}</View>
+          })}
+        </View>
+      )
+    }
+    "#,
+  );
+  assert_eq!(
+    tmpl,
+    r#"<template name="tmpl_0_f0t0"><view><view wx:for="{{i.cn}}" wx:key="sid">{{item.cn[0].v}}</view></view></template>"#
+  );
+}
+
+#[test]
+fn should_report_jsx_built_by_reduce() {
+  let mut visitor = get_visitor();
+  assert_eq!(
+    collect_diagnostics(
+      &mut visitor,
+      r#"
+      function Index () {
+        return (
+          <View compileMode>
+            {list.reduce((acc, item) => [...acc, <View>{item}</View>], [])}
+          </View>
+        )
+      }
+      "#,
+    ),
+    vec![(
+      Level::Error,
+      String::from("暂不支持使用 reduce 生成 JSX 列表，请改用 map")
+    )]
+  );
+  // 不返回 JSX 的 reduce 仍然当作普通文本处理
+  assert_eq!(
+    collect_diagnostics(
+      &mut get_visitor(),
+      r#"
+      function Index () {
+        return (
+          <View compileMode>
+            {list.reduce((acc, item) => acc + item, 0)}
+          </View>
+        )
+      }
+      "#,
+    ),
+    vec![]
+  );
+}
+
+#[test]
+fn should_extract_loop_body_from_wrapped_returns() {
+  let tmpl = get_template(
+    &mut get_visitor(),
+    r#"
+    function Index () {
+      return (
+        <View compileMode>
+          <View>{list.map(item => (/* comment */ <View>{item}</View>))}</View>
+          <View>{list.map(item => (track(item), (<View>{item}</View>)))}</View>
+        </View>
+      )
+    }
+    "#,
+  );
+  assert_eq!(
+    tmpl,
+    r#"<template name="tmpl_0_f0t0"><view><view><view wx:for="{{i.cn[0].cn}}" wx:key="sid">{{item.cn[0].v}}</view></view><view><view wx:for="{{i.cn[1].cn}}" wx:key="sid">{{item.cn[0].v}}</view></view></view></template>"#
+  );
+}
+
+#[test]
+fn should_keep_default_item_of_nested_loop() {
+  let tmpl = get_template(
+    &mut get_visitor(),
+    r#"
+    function Index () {
+      return (
+        <View compileMode>
+          {list.map(item => (
+            <View>
+              {item.children.map(item => <Text>{item.name}</Text>)}
+            </View>
+          ))}
+        </View>
+      )
+    }
+    "#,
+  );
+  assert_eq!(
+    tmpl,
+    r#"<template name="tmpl_0_f0t0"><view><view wx:for="{{i.cn}}" wx:key="sid"><text wx:for="{{item.cn}}" wx:key="sid">{{item.cn[0].v}}</text></view></view></template>"#
+  );
+}
+
+#[test]
+fn should_rename_item_of_nested_loop_with_same_name() {
+  let tmpl = get_template(
+    &mut get_visitor(),
+    r#"
+    function Index () {
+      return (
+        <View compileMode>
+          {list.map(row => (
+            <View compileForItem="row">
+              {row.children.map(row => <Text compileForItem="row">{row.name}</Text>)}
+            </View>
+          ))}
+        </View>
+      )
+    }
+    "#,
+  );
+  assert_eq!(
+    tmpl,
+    r#"<template name="tmpl_0_f0t0"><view><view wx:for="{{i.cn}}" wx:for-item="row" wx:key="sid"><text wx:for="{{row.cn}}" wx:for-item="row0" wx:key="sid">{{row0.cn[0].v}}</text></view></view></template>"#
+  );
+}
+
+#[test]
+fn should_rename_item_of_nested_loop_referencing_outer_item() {
+  let tmpl = get_template(
+    &mut get_visitor(),
+    r#"
+    function Index () {
+      return (
+        <View compileMode>
+          {list.map(group => (
+            <View>
+              {group.tags.map(tag => <Text title="{{item.title}}">{tag}</Text>)}
+            </View>
+          ))}
+        </View>
+      )
+    }
+    "#,
+  );
+  assert_eq!(
+    tmpl,
+    r#"<template name="tmpl_0_f0t0"><view><view wx:for="{{i.cn}}" wx:key="sid"><text title="{{item.title}}" wx:for="{{item.cn}}" wx:for-item="item0" wx:key="sid">{{item0.cn[0].v}}</text></view></view></template>"#
+  );
+}
+
+#[test]
+fn should_hoist_loop_body_into_named_template() {
+  let input = r#"
+    function Index () {
+      return (
+        <View compileMode>
+          {list.map(item => (
+            <View className="card">
+              <Text>{item.title}</Text>
+            </View>
+          ))}
+        </View>
+      )
+    }
+    "#;
+  assert_eq!(
+    get_template(&mut get_visitor(), input),
+    r#"<template name="tmpl_0_f0t0"><view><view class="card" wx:for="{{i.cn}}" wx:key="sid"><text>{{item.cn[0].cn[0].v}}</text></view></view></template>"#
+  );
+
+  let mut visitor = get_visitor();
+  visitor.config.hoist_loop_templates = true;
+  assert_eq!(
+    get_template(&mut visitor, input),
+    r#"<template name="tmpl_0_f0t0"><view><block wx:for="{{i.cn}}" wx:key="sid"><template is="tmpl_0_f0t0_l0" data="{{item:item,index:index,c:c,l:l}}"/></block></view></template><template name="tmpl_0_f0t0_l0"><view class="card"><text>{{item.cn[0].cn[0].v}}</text></view></template>"#
+  );
+}
+
+#[test]
+fn should_fall_back_for_loop_with_callback_reference() {
+  let mut visitor = get_visitor();
+  assert_eq!(
+    collect_diagnostics(
+      &mut visitor,
+      r#"
+      function Index () {
+        const renderItem = item => <View>{item}</View>
+        return (
+          <View compileMode>
+            {list.map(renderItem)}
+          </View>
+        )
+      }
+      "#,
+    ),
+    vec![(
+      Level::Warning,
+      String::from("循环的回调 renderItem 不是内联函数，无法提取循环体，将交由运行时渲染")
+    )]
+  );
+  assert_eq!(
+    visitor.templates.get("f0t0").unwrap(),
+    r#"<template name="tmpl_0_f0t0"><view><template is="{{xs.a(c, i.cn[0].nn, l)}}" data="{{i:i.cn[0],c:c+1,l:xs.f(l,i.cn[0].nn)}}" /></view></template>"#
+  );
+}
+
+#[test]
+fn should_mark_observed_loop_items() {
+  let tmpl = get_template(
+    &mut get_visitor(),
+    r#"
+    function Index () {
+      return (
+        <View compileMode>
+          <View>
+            {list.map(item => <View compileObserve className="card">{item.name}</View>)}
+          </View>
+          <View>
+            {list.map(item => <Image compileObserve src={item.src} />)}
+          </View>
+        </View>
+      )
+    }
+    "#,
+  );
+  assert_eq!(
+    tmpl,
+    r#"<template name="tmpl_0_f0t0"><view><view><view class="card taro-observe" id="{{item.sid}}" wx:for="{{i.cn[0].cn}}" wx:key="sid">{{item.cn[0].v}}</view></view><view><image class="taro-observe" id="{{item.sid}}" src="{{item.p3}}" wx:for="{{i.cn[1].cn}}" wx:key="sid"/></view></view></template>"#
+  );
+}
+
+#[test]
+fn should_warn_inline_objects_in_loop_bindings() {
+  let input = r#"
+    function Index () {
+      return (
+        <View compileMode>
+          <View style={{ color: 'red' }}>{title}</View>
+          {list.map(item => <View style={{ color: item.color }}>{item.name}</View>)}
+        </View>
+      )
+    }
+    "#;
+  let mut visitor = get_visitor();
+  visitor.config.warn_loop_inline_objects = true;
+  assert_eq!(
+    collect_diagnostics(&mut visitor, input),
+    vec![(
+      Level::Warning,
+      String::from("循环中的 style 属性使用了内联的对象或数组字面量，建议提取到循环外")
+    )]
+  );
+  assert!(collect_diagnostics(&mut get_visitor(), input).is_empty());
+}
+
+#[test]
+fn should_key_primitive_array_loop_by_index() {
+  let tmpl = get_template(
+    &mut get_visitor(),
+    r#"
+    function Index () {
+      return (
+        <View compileMode>
+          <View>{[1, 2, 3].map(n => <View>{n}</View>)}</View>
+          <View>{['a', 'b'].map((tab, idx) => <Text>{tab}</Text>)}</View>
+        </View>
+      )
+    }
+    "#,
+  );
+  assert_eq!(
+    tmpl,
+    r#"<template name="tmpl_0_f0t0"><view><view><view wx:for="{{i.cn[0].cn}}" wx:key="{{index}}">{{item.cn[0].v}}</view></view><view><text wx:for="{{i.cn[1].cn}}" wx:for-index="idx" wx:key="{{idx}}">{{item.cn[0].v}}</text></view></view></template>"#
+  );
+}
+
+#[test]
+fn should_move_first_child_key_onto_fragment_loop_block() {
+  let tmpl = get_template(
+    &mut get_visitor(),
+    r#"
+    function Index () {
+      return (
+        <View compileMode>
+          {list.map(item => (
+            <>
+              <View compileForKey="id">{item.title}</View>
+              <View>{item.content}</View>
+            </>
+          ))}
+        </View>
+      )
+    }
+    "#,
+  );
+  assert_eq!(
+    tmpl,
+    r#"<template name="tmpl_0_f0t0"><view><block wx:for="{{i.cn}}" wx:key="id"><view>{{item.cn[0].cn[0].v}}</view><view>{{item.cn[1].cn[0].v}}</view></block></view></template>"#
+  );
+}
+
+#[test]
+fn should_use_key_as_loop_key_when_enabled() {
+  struct KeyCounter(usize);
+  impl Visit for KeyCounter {
+    fn visit_jsx_attr(&mut self, attr: &JSXAttr) {
+      if let JSXAttrName::Ident(Ident { sym, .. }) = &attr.name {
+        if sym == "key" {
+          self.0 += 1;
+        }
+      }
+    }
+  }
+  let input = r#"
+    function Index () {
+      return (
+        <View compileMode>
+          {list.map(todo => (
+            <View key={todo.id}>
+              <Text key="label">{todo.name}</Text>
+            </View>
+          ))}
+        </View>
+      )
+    }
+    "#;
+
+  // 默认关闭：key 原样保留在 JSX 中，循环使用默认的 sid 作为 key
+  let mut visitor = get_visitor();
+  let mut module = parse_module(input);
+  module.visit_mut_with(&mut visitor);
+  assert_eq!(
+    visitor.templates.get("f0t0").unwrap(),
+    r#"<template name="tmpl_0_f0t0"><view><view wx:for="{{i.cn}}" wx:key="sid"><text>{{item.cn[0].cn[0].v}}</text></view></view></template>"#
+  );
+  let mut counter = KeyCounter(0);
+  module.visit_with(&mut counter);
+  assert_eq!(counter.0, 2);
+
+  // 开启后：循环体的 key 作为循环的 key，非循环节点上的 key 被删除
+  let mut visitor = get_visitor();
+  visitor.config.key_as_loop_key = true;
+  let mut module = parse_module(input);
+  module.visit_mut_with(&mut visitor);
+  assert_eq!(
+    visitor.templates.get("f0t0").unwrap(),
+    r#"<template name="tmpl_0_f0t0"><view><view wx:for="{{i.cn}}" wx:key="id"><text>{{item.cn[0].cn[0].v}}</text></view></view></template>"#
+  );
+  let mut counter = KeyCounter(0);
+  module.visit_with(&mut counter);
+  assert_eq!(counter.0, 1);
+}
+
+#[test]
+fn should_use_loop_index_key_when_enabled() {
+  let mut visitor = get_visitor();
+  visitor.config.key_as_loop_key = true;
+  let tmpl = get_template(
+    &mut visitor,
+    r#"
+    function Index () {
+      return (
+        <View compileMode>
+          {list.map((todo, idx) => <View key={idx}>{todo.name}</View>)}
+        </View>
+      )
+    }
+    "#,
+  );
+  assert_eq!(
+    tmpl,
+    r#"<template name="tmpl_0_f0t0"><view><view wx:for="{{i.cn}}" wx:for-index="idx" wx:key="{{idx}}">{{item.cn[0].v}}</view></view></template>"#
+  );
+}
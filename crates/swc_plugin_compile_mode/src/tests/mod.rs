@@ -1,21 +1,37 @@
 use crate::{transform::*, PluginConfig};
-use swc_core::ecma::{
-  parser,
-  visit::{as_folder, Fold},
+use std::sync::{Arc, Mutex};
+use swc_core::{
+  common::{
+    errors::{DiagnosticBuilder, Emitter, Handler, Level, HANDLER},
+    sync::Lrc,
+    FileName, SourceMap,
+  },
+  ecma::{
+    ast::{EsVersion, Module},
+    parser,
+    visit::{as_folder, Fold, VisitMutWith},
+  },
 };
 use std::env;
 
 mod attributes;
 mod children;
 mod condition;
+mod config;
 mod entry;
+mod form;
 mod harmony;
 mod looping;
+mod page;
 mod shake;
 mod wxs;
 mod skyline;
 
 pub fn tr() -> impl Fold {
+  as_folder(get_visitor())
+}
+
+pub fn get_visitor() -> TransformVisitor {
   let config = serde_json::from_str::<PluginConfig>(
     r#"
         {
@@ -68,11 +84,121 @@ pub fn tr() -> impl Fold {
                     "class": "i.cl",
                     "bindtap": "eh"
                 },
+                "input": {
+                    "value": "i.value",
+                    "placeholder": "i.p0",
+                    "bindinput": "eh",
+                    "bindfocus": "eh",
+                    "bindblur": "eh",
+                    "style": "i.st",
+                    "class": "i.cl"
+                },
+                "textarea": {
+                    "value": "i.value",
+                    "placeholder": "i.p0",
+                    "bindinput": "eh",
+                    "bindfocus": "eh",
+                    "bindblur": "eh",
+                    "style": "i.st",
+                    "class": "i.cl"
+                },
+                "movable-view": {
+                    "direction": "i.p0",
+                    "bindchange": "eh",
+                    "style": "i.st",
+                    "class": "i.cl"
+                },
                 "movable-area": {
                     "scale-area": "xs.b(i.p0,!1)",
                     "style": "i.st",
                     "class": "i.cl",
                     "bindtap": "eh"
+                },
+                "page-meta": {
+                    "page-style": "i.p0",
+                    "root-font-size": "i.p1",
+                    "bindscroll": "eh"
+                },
+                "swiper-item": {
+                    "item-id": "i.p0",
+                    "skip-hidden-item-layout": "xs.b(i.p1,!1)",
+                    "style": "i.st",
+                    "class": "i.cl"
+                },
+                "map": {
+                    "longitude": "i.p0",
+                    "latitude": "i.p1",
+                    "markers": "i.p2||[]",
+                    "polyline": "i.p3||[]",
+                    "circles": "i.p4||[]",
+                    "bindmarkertap": "eh",
+                    "style": "i.st",
+                    "class": "i.cl"
+                },
+                "picker": {
+                    "mode": "xs.b(i.p0,'selector')",
+                    "disabled": "xs.b(i.p1,!1)",
+                    "range": "i.p2",
+                    "range-key": "i.p3",
+                    "value": "i.value",
+                    "start": "i.p4",
+                    "end": "i.p5",
+                    "fields": "xs.b(i.p6,'day')",
+                    "custom-item": "i.p7",
+                    "level": "xs.b(i.p8,'region')",
+                    "bindchange": "eh",
+                    "bindcolumnchange": "eh",
+                    "bindcancel": "eh",
+                    "style": "i.st",
+                    "class": "i.cl"
+                },
+                "canvas": {
+                    "type": "i.p0",
+                    "canvas-id": "i.p1",
+                    "disable-scroll": "xs.b(i.p2,!1)",
+                    "bindtouchstart": "eh",
+                    "bindtouchmove": "eh",
+                    "bindtouchend": "eh",
+                    "bindtouchcancel": "eh",
+                    "bindlongtap": "eh",
+                    "binderror": "eh",
+                    "style": "i.st",
+                    "class": "i.cl"
+                },
+                "rich-text": {
+                    "nodes": "xs.b(i.p0,[])",
+                    "space": "i.p1",
+                    "style": "i.st",
+                    "class": "i.cl"
+                },
+                "video": {
+                    "src": "i.src",
+                    "autoplay": "xs.b(i.p0,!1)",
+                    "controls": "xs.b(i.p1,!0)",
+                    "poster": "i.p2",
+                    "bindplay": "eh",
+                    "bindpause": "eh",
+                    "bindended": "eh",
+                    "bindtimeupdate": "eh",
+                    "style": "i.st",
+                    "class": "i.cl"
+                },
+                "button": {
+                    "size": "xs.b(i.p0,'default')",
+                    "type": "i.p1",
+                    "disabled": "xs.b(i.p2,!1)",
+                    "open-type": "i.p3",
+                    "bindgetuserinfo": "eh",
+                    "bindgetphonenumber": "eh",
+                    "bindcontact": "eh",
+                    "style": "i.st",
+                    "class": "i.cl",
+                    "bindtap": "eh"
+                },
+                "navigation-bar": {
+                    "title": "i.p0",
+                    "front-color": "i.p1",
+                    "background-color": "i.p2"
                 }
             },
             "adapter": {
@@ -89,8 +215,7 @@ pub fn tr() -> impl Fold {
         }"#,
   )
   .unwrap();
-  let visitor = TransformVisitor::new(config);
-  as_folder(visitor)
+  TransformVisitor::new(config)
 }
 
 pub fn get_syntax_config() -> parser::Syntax {
@@ -103,3 +228,52 @@ pub fn get_syntax_config() -> parser::Syntax {
     ..Default::default()
   })
 }
+
+pub fn parse_module(input: &str) -> Module {
+  let cm: Lrc<SourceMap> = Default::default();
+  let fm = cm.new_source_file(FileName::Anon, input.to_string());
+  parser::parse_file_as_module(
+    &fm,
+    get_syntax_config(),
+    EsVersion::latest(),
+    None,
+    &mut vec![],
+  )
+  .unwrap()
+}
+
+pub fn get_template(visitor: &mut TransformVisitor, input: &str) -> String {
+  let mut module = parse_module(input);
+  module.visit_mut_with(visitor);
+  visitor.templates.get("f0t0").unwrap().clone()
+}
+
+struct CollectingEmitter(Arc<Mutex<Vec<(Level, String)>>>);
+
+impl Emitter for CollectingEmitter {
+  fn emit(&mut self, db: &DiagnosticBuilder<'_>) {
+    let label = db
+      .span
+      .span_labels()
+      .into_iter()
+      .filter_map(|label| label.label)
+      .collect::<Vec<_>>()
+      .join("");
+    self.0.lock().unwrap().push((db.level, label));
+  }
+}
+
+// 编译模板并收集过程中输出的诊断信息：(级别, 标签内容)
+pub fn collect_diagnostics(visitor: &mut TransformVisitor, input: &str) -> Vec<(Level, String)> {
+  let diagnostics = Arc::new(Mutex::new(vec![]));
+  let handler = Handler::with_emitter(
+    true,
+    false,
+    Box::new(CollectingEmitter(diagnostics.clone())),
+  );
+  HANDLER.set(&handler, || {
+    get_template(visitor, input);
+  });
+  let diagnostics = diagnostics.lock().unwrap().clone();
+  diagnostics
+}
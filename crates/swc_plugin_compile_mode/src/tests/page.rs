@@ -0,0 +1,47 @@
+use super::{get_template, get_visitor};
+
+#[test]
+fn should_support_page_meta() {
+  let tmpl = get_template(
+    &mut get_visitor(),
+    r##"
+    import { PageMeta, NavigationBar } from '@tarojs/components'
+
+    function Index () {
+      return (
+        <View compileMode>
+          <PageMeta pageStyle={pageStyle} rootFontSize="16px">
+            <NavigationBar title={title} frontColor="#ffffff" />
+          </PageMeta>
+        </View>
+      )
+    }
+    "##,
+  );
+  assert_eq!(
+    tmpl,
+    r##"<template name="tmpl_0_f0t0"><view><page-meta page-style="{{i.cn[0].p0}}" root-font-size="16px"><navigation-bar front-color="#ffffff" title="{{i.cn[0].cn[0].p0}}"></navigation-bar></page-meta></view></template>"##
+  );
+}
+
+#[test]
+fn should_support_aliased_page_meta() {
+  let tmpl = get_template(
+    &mut get_visitor(),
+    r#"
+    import { PageMeta as Meta } from '@tarojs/components'
+
+    function Index () {
+      return (
+        <View compileMode>
+          <Meta pageStyle="overflow: hidden;" />
+        </View>
+      )
+    }
+    "#,
+  );
+  assert_eq!(
+    tmpl,
+    r#"<template name="tmpl_0_f0t0"><view><page-meta page-style="overflow: hidden;"></page-meta></view></template>"#
+  );
+}
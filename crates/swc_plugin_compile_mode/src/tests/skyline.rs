@@ -1,4 +1,5 @@
-use super::{get_syntax_config, tr};
+use super::{get_syntax_config, get_template, get_visitor, tr};
+use std::collections::HashMap;
 use swc_core::ecma::transforms::testing::test;
 
 test!(
@@ -38,3 +39,183 @@ test!(
     }
     "#
 );
+
+test!(
+  get_syntax_config(),
+  |_| tr(),
+  should_keep_list_attrs_in_source_order,
+  r#"
+    import { List } from '@tarojs/components'
+    function Index () {
+        return (
+          <List
+            compileMode
+            type="static"
+            lowerThresholdCount={20}
+            childCount={list.length}
+            onScrollCatch={handleScroll}
+            className="feed"
+            padding={gap}
+            scrollY
+          />
+        )
+    }
+    "#
+);
+
+#[test]
+fn should_convert_list_scroll_events() {
+  let mut visitor = get_visitor();
+  visitor.config.components.insert(
+    String::from("scroll-view"),
+    HashMap::from([
+      (String::from("upper-threshold"), String::from("i.p0")),
+      (String::from("lower-threshold"), String::from("i.p1")),
+      (String::from("bindscrolltoupper"), String::from("eh")),
+      (String::from("bindscrolltolower"), String::from("eh")),
+    ]),
+  );
+  visitor
+    .config
+    .components
+    .insert(String::from("list-builder"), HashMap::new());
+  let tmpl = get_template(
+    &mut visitor,
+    r#"
+    import { List } from '@tarojs/components'
+    function Index () {
+      return (
+        <View compileMode>
+          <List lowerThresholdCount={20} onScrollToLower={loadMore} onScrollToUpper={refresh}></List>
+        </View>
+      )
+    }
+    "#,
+  );
+  assert_eq!(
+    tmpl,
+    r#"<template name="tmpl_0_f0t0"><view><scroll-view bindscrolltolower="eh" bindscrolltoupper="eh" data-sid="{{i.cn[0].sid}}" id="{{i.cn[0].sid}}" lower-threshold="{{i.cn[0].p1}}" type="custom"><list-builder class="list-builder"></list-builder></scroll-view></view></template>"#
+  );
+}
+
+#[test]
+fn should_convert_list_catch_scroll_events() {
+  let mut visitor = get_visitor();
+  visitor
+    .config
+    .components
+    .insert(String::from("scroll-view"), HashMap::new());
+  visitor
+    .config
+    .components
+    .insert(String::from("list-builder"), HashMap::new());
+  let tmpl = get_template(
+    &mut visitor,
+    r#"
+    import { List } from '@tarojs/components'
+    function Index () {
+      return (
+        <View compileMode>
+          <List onScrollCatch={handleScroll} catchScrollToLower={loadMore}></List>
+        </View>
+      )
+    }
+    "#,
+  );
+  assert_eq!(
+    tmpl,
+    r#"<template name="tmpl_0_f0t0"><view><scroll-view catchscroll="eh" catchscrolltolower="eh" data-sid="{{i.cn[0].sid}}" id="{{i.cn[0].sid}}" type="custom"><list-builder class="list-builder"></list-builder></scroll-view></view></template>"#
+  );
+}
+
+#[test]
+fn should_pass_extra_scroll_view_props_from_config() {
+  let mut visitor = get_visitor();
+  visitor.config.extra_scroll_view_props = vec![String::from("bounces")];
+  visitor.config.components.insert(
+    String::from("scroll-view"),
+    HashMap::from([(String::from("bounces"), String::from("xs.b(i.p0,!0)"))]),
+  );
+  visitor
+    .config
+    .components
+    .insert(String::from("list-builder"), HashMap::new());
+  let tmpl = get_template(
+    &mut visitor,
+    r#"
+    import { List } from '@tarojs/components'
+    function Index () {
+      return (
+        <View compileMode>
+          <List bounces={false} fastDeceleration></List>
+        </View>
+      )
+    }
+    "#,
+  );
+  assert_eq!(
+    tmpl,
+    r#"<template name="tmpl_0_f0t0"><view><scroll-view bounces="{{!!xs.b(i.cn[0].p0,!0)&&xs.b(i.cn[0].p0,!0)!=='false'}}" type="custom"><list-builder class="list-builder"></list-builder></scroll-view></view></template>"#
+  );
+}
+
+#[test]
+fn should_hoist_refresher_slot_out_of_list_builder() {
+  let mut visitor = get_visitor();
+  visitor
+    .config
+    .components
+    .insert(String::from("scroll-view"), HashMap::new());
+  visitor
+    .config
+    .components
+    .insert(String::from("list-builder"), HashMap::new());
+  let tmpl = get_template(
+    &mut visitor,
+    r#"
+    import { List } from '@tarojs/components'
+    function Index () {
+      return (
+        <View compileMode>
+          <List>
+            <View slot="refresher">refreshing</View>
+            <View>{content}</View>
+          </List>
+        </View>
+      )
+    }
+    "#,
+  );
+  assert_eq!(
+    tmpl,
+    r#"<template name="tmpl_0_f0t0"><view><scroll-view type="custom"><view slot="refresher">refreshing</view><list-builder class="list-builder"><view>{{i.cn[0].cn[0].cn[0].cn[0].v}}</view></list-builder></scroll-view></view></template>"#
+  );
+}
+
+#[test]
+fn should_add_px_unit_to_numeric_list_thresholds() {
+  let input = r#"
+    import { List } from '@tarojs/components'
+    function Index () {
+      return (
+        <View compileMode>
+          <List upperThresholdCount={20} lowerThresholdCount="30px"></List>
+        </View>
+      )
+    }
+    "#;
+  let mut visitor = get_visitor();
+  visitor.config.platform = String::from("SWAN");
+  visitor
+    .config
+    .components
+    .insert(String::from("scroll-view"), HashMap::new());
+  visitor
+    .config
+    .components
+    .insert(String::from("list-builder"), HashMap::new());
+  assert_eq!(
+    get_template(&mut visitor, input),
+    r#"<template name="tmpl_0_f0t0"><view><scroll-view lower-threshold="30px" type="custom" upper-threshold="20px"><list-builder class="list-builder"></list-builder></scroll-view></view></template>"#
+  );
+}
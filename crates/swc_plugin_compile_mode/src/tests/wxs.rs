@@ -1,4 +1,4 @@
-use super::{get_syntax_config, tr};
+use super::{get_syntax_config, get_template, get_visitor, tr};
 use swc_core::ecma::transforms::testing::test;
 
 test!(
@@ -77,3 +77,27 @@ test!(
     }
     "#
 );
+
+#[test]
+fn should_support_wxs_calls_with_arguments() {
+  let tmpl = get_template(
+    &mut get_visitor(),
+    r#"
+    function Index () {
+      return (
+        <View compileMode>
+          <Script src="./logic.wxs" module="logic"></Script>
+          <View hoverClass={logic.fn(x)}>A</View>
+          <View hoverClass={logic.a.fn(x)}>B</View>
+          <View hoverClass={logic.a.fn(logic.b.c('d'), y)}>C</View>
+          <View>{logic.fn(logic.a.fn(1), 'e')}</View>
+        </View>
+      )
+    }
+    "#,
+  );
+  assert_eq!(
+    tmpl,
+    r#"<template name="tmpl_0_f0t0"><view><wxs module="logic" src="./logic.wxs"></wxs><view hover-class="{{logic.fn(i.cn[0].xs0)}}">A</view><view hover-class="{{logic.a.fn(i.cn[1].xs0)}}">B</view><view hover-class="{{logic.a.fn(logic.b.c('d'),i.cn[2].xs0)}}">C</view><view>{{logic.fn(logic.a.fn(1),"e")}}</view></view></template>"#
+  );
+}
@@ -1,6 +1,9 @@
 use crate::utils::{self, constants::*, transform_taro_components};
 use crate::{utils::as_xscript_expr_string, PluginConfig};
-use std::collections::HashMap;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::rc::Rc;
 use std::vec;
 use swc_core::{
   atoms::Atom,
@@ -20,21 +23,33 @@ struct PreVisitor {
   // HashMap<导出名, 别名>
   // import { x as y } from 'pkg'; import_aliases: [[x -> y]]
   pub import_aliases: HashMap<String, String>,
+  // HashMap<组件名, HashMap<属性名, 默认值>>
+  pub component_default_attrs: HashMap<String, HashMap<String, String>>,
+  // List 额外透传给 scroll-view 的属性
+  pub extra_scroll_view_props: Vec<String>,
+  pub platform: String,
 }
 
 impl PreVisitor {
   fn new(
     import_specifiers: HashMap<String, String>,
     import_aliases: HashMap<String, String>,
+    component_default_attrs: HashMap<String, HashMap<String, String>>,
+    extra_scroll_view_props: Vec<String>,
+    platform: String,
   ) -> Self {
     Self {
       import_specifiers,
       import_aliases,
+      component_default_attrs,
+      extra_scroll_view_props,
+      platform,
     }
   }
 }
 impl VisitMut for PreVisitor {
   fn visit_mut_jsx_element_children(&mut self, children: &mut Vec<JSXElementChild>) {
+    utils::expand_jsx_array_children(children);
     let len = children.len();
 
     // 当 JSX 循环表达式存在兄弟节点，且这些兄弟节点中有动态节点（存在 JSX 表达式）时，
@@ -177,6 +192,8 @@ impl VisitMut for PreVisitor {
                 })
               }
               _ => {
+                // 条件包裹循环时，compileIf 与 compileFor 放在同一个循环体上
+                let is_loop_with_if = utils::inject_compile_if_into_loop(left, right);
                 let jsx_el_name = JSXElementName::Ident(quote_ident!("block"));
                 let mut block = Box::new(JSXElement {
                   span,
@@ -196,7 +213,9 @@ impl VisitMut for PreVisitor {
                     name: jsx_el_name.clone(),
                   }),
                 });
-                inject_compile_if(&mut block, left);
+                if !is_loop_with_if {
+                  inject_compile_if(&mut block, left);
+                }
                 **expr =
                   get_element_double(jsx_el_name, left, &mut Box::new(Expr::JSXElement(block)));
               }
@@ -213,6 +232,7 @@ impl VisitMut for PreVisitor {
               el.opening.attrs.push(attr);
             }
             _ => {
+              // 循环等非元素分支包裹 <block>，由 block 承载 compileIf、compileElse，循环作为其子节点
               let temp = arm.take();
               let jsx_el_name = JSXElementName::Ident(quote_ident!("block"));
               **arm = Expr::JSXElement(Box::new(JSXElement {
@@ -248,8 +268,50 @@ impl VisitMut for PreVisitor {
   }
 
   fn visit_mut_jsx_element(&mut self, el: &mut JSXElement) {
+    // compileIgnore 的子树不会输出到模板，跳过整棵子树
+    if utils::check_jsx_element_has_compile_ignore(el) {
+      return;
+    }
+    // Suspense 在模板中没有对应实现，展开为其子节点
+    if utils::is_suspense_element(el) {
+      HANDLER.with(|handler| {
+        handler
+          .struct_span_warn(el.span, "Taro CompileMode 提示")
+          .span_label(
+            el.span,
+            "Suspense 在模板中没有对应实现，已展开为其子节点，fallback 不会生效",
+          )
+          .emit();
+      });
+      utils::flatten_suspense(el);
+    }
     // 处理 @tarojs/components 的 List,ListItem 组件
-    transform_taro_components(el, &self.import_specifiers, &self.import_aliases);
+    let removed_attrs = transform_taro_components(
+      el,
+      &self.import_specifiers,
+      &self.import_aliases,
+      &self.extra_scroll_view_props,
+      &self.platform,
+    );
+    for (attr_span, label) in removed_attrs {
+      HANDLER.with(|handler| {
+        handler
+          .struct_span_warn(attr_span, "Taro CompileMode 提示")
+          .span_label(attr_span, &label)
+          .emit();
+      });
+    }
+    // 注入配置的组件默认属性
+    utils::inject_component_default_attrs(el, &self.component_default_attrs);
+    // 处理 classnames 风格的数组 className 以及 classNames()、cx() 调用
+    utils::transform_class_array_attrs(&mut el.opening.attrs);
+    utils::transform_class_names_call_attrs(&mut el.opening.attrs);
+    // 删除空的 className、style
+    utils::remove_empty_class_style_attrs(&mut el.opening.attrs);
+    // 处理 dangerouslySetInnerHTML
+    utils::transform_inner_html(el);
+    // Text 中的行内条件文本
+    utils::wrap_inline_cond_text(el);
     el.visit_mut_children_with(self);
   }
 }
@@ -267,10 +329,32 @@ pub struct TransformVisitor {
   // HashMap<导出名, 别名>
   // import { x as y } from 'pkg'; import_aliases: [[x -> y]]
   pub import_aliases: HashMap<String, String>,
+  // 由外到内各层循环的 for-item 名称
+  pub loop_item_names: Vec<String>,
+  // 由外到内各层循环的 for-item 是否通过 compileForItem 指定
+  pub loop_item_explicit: Vec<bool>,
+  // 纯静态子树的模板片段缓存：HashMap<配置与结构描述, 模板片段>
+  // 配置相同的多个 TransformVisitor 可以共享同一份缓存
+  pub static_fragment_cache: StaticFragmentCache,
+  // 当前 compileMode 节点的模板名，以及从中提取出的循环体模板
+  pub tmpl_name: String,
+  pub hoisted_templates: Vec<String>,
+  // 正在编译的渲染函数名，用于发现渲染函数递归调用自身
+  pub render_fn_names: HashSet<String>,
+  // inline_simple_exprs 生成的内联 wxs 函数，输出到模板的内联模块中
+  pub inline_xs_fns: Vec<String>,
 }
 
+pub type StaticFragmentCache = Rc<RefCell<HashMap<String, String>>>;
+
 impl TransformVisitor {
-  pub fn new(config: PluginConfig) -> Self {
+  pub fn new(mut config: PluginConfig) -> Self {
+    utils::normalize_component_names(&mut config.components);
+    utils::normalize_component_names(&mut config.component_default_attrs);
+    // 未配置的模板语法使用平台默认值
+    for (key, value) in utils::default_adapter(&config.platform) {
+      config.adapter.entry(key).or_insert(value);
+    }
     let get_tmpl_name = Box::new(utils::named_iter(format!("{}t", config.tmpl_prefix)));
     Self {
       config,
@@ -282,9 +366,23 @@ impl TransformVisitor {
       xs_sources: vec![],
       import_specifiers: HashMap::new(),
       import_aliases: HashMap::new(),
+      loop_item_names: vec![],
+      loop_item_explicit: vec![],
+      static_fragment_cache: Rc::new(RefCell::new(HashMap::new())),
+      tmpl_name: String::new(),
+      hoisted_templates: vec![],
+      render_fn_names: HashSet::new(),
+      inline_xs_fns: vec![],
     }
   }
 
+  /**
+   * 把已生成的模板按模板名顺序逐个写入 sink，适合输出体积较大的场景
+   */
+  pub fn write_templates(&self, sink: &mut impl fmt::Write) -> fmt::Result {
+    utils::write_templates(&self.templates, sink)
+  }
+
   fn collect_import_info(&mut self, body_stmts: &mut Vec<ModuleItem>) {
     body_stmts.iter().for_each(|item| match item {
       ModuleItem::ModuleDecl(ModuleDecl::Import(import_decl)) => {
@@ -324,6 +422,45 @@ impl TransformVisitor {
   }
 
   fn build_xml_element(&mut self, el: &mut JSXElement) -> String {
+    if let Some(name) = utils::get_unregistered_taro_component(
+      el,
+      &self.import_specifiers,
+      &self.import_aliases,
+      &self.config,
+    ) {
+      match &self.config.unregistered_component_fallback {
+        // 改用配置的组件渲染，JSX 中同步替换，保证运行时节点与模板一致
+        Some(tag) if self.config.components.contains_key(tag) => {
+          utils::transform_tag_component(el, tag);
+        }
+        _ => {
+          HANDLER.with(|handler| {
+            handler
+              .struct_span_warn(el.span, "Taro CompileMode 提示")
+              .span_label(
+                el.span,
+                &format!("当前平台没有注册 {} 组件，将交由运行时渲染", name),
+              )
+              .emit();
+          });
+        }
+      }
+    }
+    // 当前节点的嵌套层级为 node_stack.len() + 1，只在每条分支第一个超出的节点上提示
+    if self.config.max_depth > 0 && self.node_stack.len() == self.config.max_depth {
+      HANDLER.with(|handler| {
+        handler
+          .struct_span_warn(el.span, "Taro CompileMode 提示")
+          .span_label(
+            el.span,
+            &format!(
+              "模板嵌套层级超过了配置的最大层级 {}，部分平台可能无法正常渲染",
+              self.config.max_depth
+            ),
+          )
+          .emit();
+      });
+    }
     let is_inner_component = utils::is_inner_component(&el, &self.config);
     let opening_element = &mut el.opening;
 
@@ -346,6 +483,26 @@ impl TransformVisitor {
           );
         }
 
+        // CustomWrapper 的子树由 custom-wrapper 自定义组件在运行时渲染，这里只输出组件本身
+        if ident.sym == CUSTOM_WRAPPER_TAG {
+          let node_path = self.get_current_node_path();
+          let xs_attr = if self.config.is_use_xs {
+            r#" l="{{l}}""#
+          } else {
+            ""
+          };
+          return format!(
+            r#"<{} i="{{{{{}}}}}"{} id="{{{{{}.uid||{}.sid}}}}" data-sid="{{{{{}.sid}}}}"></{}>"#,
+            CUSTOM_WRAPPER_TAG,
+            node_path,
+            xs_attr,
+            node_path,
+            node_path,
+            node_path,
+            CUSTOM_WRAPPER_TAG
+          );
+        }
+
         if is_inner_component {
           // 内置组件
           let mut name = utils::to_kebab_case(ident.as_ref());
@@ -353,8 +510,27 @@ impl TransformVisitor {
           if attrs.is_none() {
             return String::new();
           };
+          // 循环等表达式子节点依赖外层 block 包裹，只有唯一有效子节点为元素时才可以省略
+          let is_single_element_child = utils::get_valid_nodes(&el.children) == 1
+            && el.children.iter().any(|child| {
+              matches!(child, JSXElementChild::JSXElement(_)) && utils::is_valid_node(child)
+            });
           let (children, ..) = self.build_xml_children(&mut el.children, None);
 
+          // 不带任何属性、条件的 block 只包裹了一个元素子节点时，模板中无需输出这层 block
+          // 节点路径按节点树计算，不受影响
+          if name == BLOCK_TAG && is_single_element_child && attrs.as_deref() == Some("") {
+            return children;
+          }
+
+          // 部分平台使用其它标签作为不渲染的包裹节点
+          if name == BLOCK_TAG {
+            if let Some(block) = self.config.adapter.get(BLOCK_TAG) {
+              name = block.to_string();
+            }
+          }
+
+          let is_void = children.is_empty() && VOID_TAGS.contains(&name.as_str());
           if utils::is_xscript(&name) {
             name = match self.config.adapter.get("xs") {
               Some(xs) => xs.to_string(),
@@ -366,15 +542,21 @@ impl TransformVisitor {
                 panic!()
               }),
             };
+          } else {
+            name = utils::convert_tag_case(&name, &self.config.tag_case);
           }
 
-          format!(
-            "<{}{}>{}</{}>",
-            name,
-            attrs.unwrap_or_default(),
-            children,
-            name
-          )
+          if is_void {
+            format!("<{}{}/>", name, attrs.unwrap_or_default())
+          } else {
+            format!(
+              "<{}{}>{}</{}>",
+              name,
+              attrs.unwrap_or_default(),
+              children,
+              name
+            )
+          }
         } else {
           // 回退到旧的渲染模式（React 组件、原生自定义组件）
           // 如果是 map React组件，那么组件经过 extract_jsx_loop 的处理后会有 compileFor 属性，可以检测这个属性判断当前组件是否是循环里的组件
@@ -422,18 +604,162 @@ impl TransformVisitor {
     let is_xscript = utils::is_xscript(element_name);
     let mut attrs_wait_for_inserting: Vec<JSXAttrOrSpread> = vec![];
     let mut get_xs_attrs_name = utils::named_iter("xs".into());
+    let ignored_attrs = utils::take_compile_ignore_attrs(opening_element);
+    let mut is_observed = false;
+    let mut inline_xs_fns = std::mem::take(&mut self.inline_xs_fns);
+    let is_loop_body = opening_element.attrs.iter().any(|attr| {
+      matches!(attr, JSXAttrOrSpread::JSXAttr(JSXAttr {
+        name: JSXAttrName::Ident(Ident { sym, .. }),
+        ..
+      }) if sym == COMPILE_FOR)
+    });
     opening_element.attrs.retain_mut(|attr| {
       if let JSXAttrOrSpread::JSXAttr(jsx_attr) = attr {
+        if let JSXAttrName::Ident(Ident { sym, .. }) = &jsx_attr.name {
+          // compileIgnore 中列出的属性不输出到模板，保留在 JSX 中
+          if ignored_attrs.contains(&sym.to_string()) {
+            return true;
+          }
+          if sym == COMPILE_OBSERVE {
+            is_observed = true;
+            return false;
+          }
+        }
+
+        // raw:xxx 跳过属性名、事件名的转换，原样输出
+        if let Some(raw_attr_name) = utils::get_raw_attr_name(&jsx_attr.name) {
+          match &jsx_attr.value {
+            Some(JSXAttrValue::Lit(Lit::Str(Str { value, .. }))) => {
+              props.insert(raw_attr_name, value.to_string());
+              return false;
+            }
+            Some(JSXAttrValue::JSXExprContainer(..)) => {
+              let node_path = self.get_current_node_path();
+              let miniapp_attr_value =
+                utils::gen_template(&format!("{}.{}", node_path, raw_attr_name));
+              props.insert(raw_attr_name.clone(), miniapp_attr_value);
+              // 运行时以去掉前缀的属性名取值
+              jsx_attr.name = JSXAttrName::Ident(quote_ident!(raw_attr_name));
+              return true;
+            }
+            None => {
+              props.insert(raw_attr_name, String::from("true"));
+              return false;
+            }
+            _ => return true,
+          }
+        }
+
+        // markId、mark-id、mark:id 输出为小程序的 mark:id，不做 kebab-case 转换
+        if let Some((jsx_mark_name, mark_attr_name)) = utils::get_mark_attr_name(&jsx_attr.name) {
+          match &jsx_attr.value {
+            Some(JSXAttrValue::Lit(Lit::Str(Str { value, .. }))) => {
+              props.insert(mark_attr_name, value.to_string());
+              return false;
+            }
+            Some(JSXAttrValue::JSXExprContainer(..)) => {
+              let node_path = self.get_current_node_path();
+              let miniapp_attr_value =
+                utils::gen_template(&format!("{}.{}", node_path, jsx_mark_name));
+              props.insert(mark_attr_name, miniapp_attr_value);
+              // 运行时统一以驼峰的属性名取值
+              jsx_attr.name = JSXAttrName::Ident(quote_ident!(jsx_mark_name));
+              return true;
+            }
+            None => {
+              props.insert(mark_attr_name, String::from("true"));
+              return false;
+            }
+            _ => return true,
+          }
+        }
+
+        // catchTap、catch:tap 直接输出为平台的 catch 事件，bindtap、bind:tap 原样输出，运行时按 onXxx 事件处理
+        let authored_event =
+          utils::identify_jsx_catch_event_key(&jsx_attr.name, &self.config.platform)
+            .or_else(|| utils::identify_jsx_bind_event_key(&jsx_attr.name));
+        if let Some((jsx_event_name, miniapp_event_name)) = authored_event {
+          match &jsx_attr.value {
+            Some(JSXAttrValue::Lit(Lit::Str(Str { value, .. }))) => {
+              props.insert(miniapp_event_name, value.to_string());
+              return false;
+            }
+            Some(JSXAttrValue::JSXExprContainer(..)) => {
+              let node_path = self.get_current_node_path();
+              props.insert(miniapp_event_name, String::from(EVENT_HANDLER));
+              if props.get(DATA_SID).is_none() {
+                props.insert(String::from(DATA_SID), format!("{{{{{}.sid}}}}", node_path));
+              }
+              jsx_attr.name = JSXAttrName::Ident(quote_ident!(jsx_event_name));
+              return true;
+            }
+            _ => (),
+          }
+        }
+
+        // onclick 等全小写的事件名按 onClick 处理，运行时同样使用 onClick
+        if self.config.lowercase_events {
+          if let JSXAttrName::Ident(Ident { sym: name, .. }) = &jsx_attr.name {
+            if let Some(event_name) = utils::normalize_lowercase_event_key(name) {
+              jsx_attr.name = JSXAttrName::Ident(quote_ident!(event_name));
+            }
+          }
+        }
+
         if let JSXAttrName::Ident(Ident { sym: name, .. }) = &jsx_attr.name {
           let jsx_attr_name = name.to_string();
 
+          // key、ref 只在运行时使用：保留在 JSX 中交给 React 处理，不输出到模板
           if REACT_RESERVED.contains(&jsx_attr_name.as_str()) {
-            return true;
+            // 非循环节点上的 key 没有意义，开启 key_as_loop_key 时直接删除
+            return !(jsx_attr_name == "key" && self.config.key_as_loop_key && !is_loop_body);
           }
 
-          let miniapp_attr_name = utils::convert_jsx_attr_key(&jsx_attr_name, &self.config.adapter);
-          let event_name = utils::identify_jsx_event_key(&jsx_attr_name, &self.config.platform);
+          // Button 的 open-type 只支持平台规定的取值，书写错误时不会生效
+          if element_name == BUTTON_TAG && jsx_attr_name == "openType" {
+            if let Some(JSXAttrValue::Lit(Lit::Str(Str { value, .. }))) = &jsx_attr.value {
+              for open_type in utils::get_invalid_open_types(value, &self.config.platform) {
+                HANDLER.with(|handler| {
+                  handler
+                    .struct_span_warn(jsx_attr.span, "Taro CompileMode 提示")
+                    .span_label(
+                      jsx_attr.span,
+                      &format!("当前平台的 Button 不支持 open-type=\"{}\"", open_type),
+                    )
+                    .emit();
+                });
+              }
+            }
+          }
+
+          let attr_key = utils::convert_form_control_attr_key(element_name, &jsx_attr_name);
+          let miniapp_attr_name = utils::escape_reserved_attr_key(
+            attr_key,
+            utils::convert_text_select_attr_key(
+              element_name,
+              utils::convert_jsx_attr_key(attr_key, &self.config.adapter),
+              &self.config.platform,
+            ),
+            &self.config.platform,
+          );
+          let event_prefix = utils::get_event_prefix(&self.config.adapter);
+          let event_name = utils::convert_form_control_focus_event(
+            element_name,
+            attr_key,
+            &self.config.platform,
+            event_prefix,
+          )
+          .or_else(|| utils::identify_jsx_event_key(attr_key, &self.config.platform, event_prefix));
           let is_event = event_name.is_some();
+          // touchforcechange、CSS 动画与过渡等事件只有部分平台支持，其余平台给出提示
+          if is_event && !utils::is_event_supported(attr_key, &self.config.platform) {
+            HANDLER.with(|handler| {
+              handler
+                .struct_span_warn(jsx_attr.span, "Taro CompileMode 提示")
+                .span_label(jsx_attr.span, &format!("当前平台不支持 {} 事件", attr_key))
+                .emit();
+            });
+          }
           match &mut jsx_attr.value {
             Some(jsx_attr_value) => {
               match jsx_attr_value {
@@ -447,15 +773,67 @@ impl TransformVisitor {
                     }
                   }
 
+                  // 循环的 key 区分字段名和表达式
+                  if jsx_attr_name == COMPILE_FOR_KEY {
+                    props.insert(
+                      miniapp_attr_name,
+                      utils::lower_template_bindings(
+                        &utils::gen_loop_key(value),
+                        &self.config.platform,
+                      ),
+                    );
+                    return false;
+                  }
+
+                  // 布尔类型的属性写成 "false" 时，模板中的字符串 "false" 是真值，输出为布尔值
+                  if value == "false"
+                    && attrs_map
+                      .get(&miniapp_attr_name)
+                      .is_some_and(|attr_value| utils::is_boolean_attr_value(attr_value))
+                  {
+                    props.insert(miniapp_attr_name, utils::gen_template("false"));
+                    return false;
+                  }
+
                   // 静态属性在 xml 中保留即可，jsx 中可以删除
                   if jsx_attr_name != COMPILE_MODE {
-                    props.insert(miniapp_attr_name, value.to_string());
+                    // 只有 style 和尺寸类属性按 UnitPolicy 转换 px
+                    let value = if utils::is_unit_attr(&miniapp_attr_name) {
+                      utils::transform_unit(value, &self.config.unit_policy)
+                    } else {
+                      value.to_string()
+                    };
+                    // 迁移代码中手写的 {{...}} 绑定按平台改写不支持的语法
+                    props.insert(
+                      miniapp_attr_name,
+                      utils::lower_template_bindings(&value, &self.config.platform),
+                    );
                     return false;
                   }
                 }
                 JSXAttrValue::JSXExprContainer(JSXExprContainer { expr: jsx_expr, .. }) => {
                   let mut node_path = self.get_current_node_path();
 
+                  // 循环中的内联对象、数组字面量每一项都会重新创建
+                  if self.config.warn_loop_inline_objects && !self.loop_item_names.is_empty() {
+                    if let JSXExpr::Expr(expr) = jsx_expr {
+                      if expr.is_object() || expr.is_array() {
+                        HANDLER.with(|handler| {
+                          handler
+                            .struct_span_warn(jsx_attr.span, "Taro CompileMode 提示")
+                            .span_label(
+                              jsx_attr.span,
+                              &format!(
+                                "循环中的 {} 属性使用了内联的对象或数组字面量，建议提取到循环外",
+                                jsx_attr_name
+                              ),
+                            )
+                            .emit();
+                        });
+                      }
+                    }
+                  }
+
                   // 处理 wxs 表达式属性
                   if self.is_xscript_used() {
                     if let JSXExpr::Expr(expr) = jsx_expr {
@@ -517,6 +895,14 @@ impl TransformVisitor {
                                       }
                                     }
                                     _ => {
+                                      // wxs 表达式作为参数时直接在模板中求值
+                                      if let Some(arg_string) = utils::as_xscript_arg_string(
+                                        &arg.expr,
+                                        &self.xs_module_names,
+                                        '\'',
+                                      ) {
+                                        return arg_string;
+                                      }
                                       // 表达式
                                       let name = get_xs_attrs_name();
                                       let expr = arg.expr.take();
@@ -564,6 +950,54 @@ impl TransformVisitor {
                     return true;
                   }
 
+                  // 简单的字符串、数字运算生成内联 wxs 函数在模板中计算，运行时只需要传递其中的变量
+                  if self.config.inline_simple_exprs
+                    && INLINE_XS_PLATFORMS.contains(&self.config.platform.as_str())
+                    && !utils::is_compile_directive(&jsx_attr_name)
+                  {
+                    if let JSXExpr::Expr(expr) = jsx_expr {
+                      let inline_expr = utils::as_inline_expr_string(
+                        expr,
+                        &mut get_xs_attrs_name,
+                        &mut attrs_wait_for_inserting,
+                      );
+                      if let Some((body, params)) = inline_expr {
+                        let inline_fn =
+                          format!("function({}){{return {}}}", params.join(","), body);
+                        // 函数体相同的表达式复用同一个函数
+                        let fn_idx = match inline_xs_fns.iter().position(|f| *f == inline_fn) {
+                          Some(idx) => idx,
+                          None => {
+                            inline_xs_fns.push(inline_fn);
+                            inline_xs_fns.len() - 1
+                          }
+                        };
+                        let args: Vec<String> = params
+                          .iter()
+                          .map(|param| format!("{}.{}", node_path, param))
+                          .collect();
+                        props.insert(
+                          miniapp_attr_name,
+                          utils::gen_template(&format!(
+                            "{}.f{}({})",
+                            INLINE_XS_MODULE,
+                            fn_idx,
+                            args.join(",")
+                          )),
+                        );
+                        return false;
+                      }
+                    }
+                  }
+
+                  // movable-view、movable-area 的 catch-move、disable-scroll 不在组件属性白名单中，直接取属性名
+                  if utils::is_movable_passthrough_attr(element_name, &miniapp_attr_name) {
+                    let miniapp_attr_value =
+                      utils::gen_template(&format!("{}.{}", node_path, jsx_attr_name));
+                    props.insert(miniapp_attr_name, miniapp_attr_value);
+                    return true;
+                  }
+
                   // 小程序组件标准属性 -> 取 @tarojs/shared 传递过来的属性值；非标准属性 -> 取属性名
                   let value: &str = attrs_map
                     .get(&miniapp_attr_name)
@@ -589,6 +1023,12 @@ impl TransformVisitor {
                   } else {
                     format!("{}{}", node_path, value)
                   };
+                  // 布尔类型的属性运行时可能传入字符串 "false"，需要转换为布尔值
+                  let value = if utils::is_boolean_attr_value(&value) {
+                    utils::gen_boolean_binding(&value)
+                  } else {
+                    value
+                  };
                   // 得出最终的模板属性值
                   let miniapp_attr_value = utils::gen_template(&value);
 
@@ -617,6 +1057,8 @@ impl TransformVisitor {
       return true;
     });
 
+    self.inline_xs_fns = inline_xs_fns;
+
     // 插入需要额外放进到 JSX Attribute 的属性
     for item in attrs_wait_for_inserting {
       opening_element.attrs.push(item)
@@ -627,6 +1069,19 @@ impl TransformVisitor {
       props.insert(String::from(ID), props.get(DATA_SID).unwrap().clone());
     }
 
+    // compileObserve：输出供 IntersectionObserver 选择节点的类名，没有设置 id 时把 id 设为 sid
+    if is_observed {
+      let class = match props.get("class") {
+        Some(class) => format!("{} {}", class, OBSERVE_CLASS),
+        None => String::from(OBSERVE_CLASS),
+      };
+      props.insert(String::from("class"), class);
+      if props.get(ID).is_none() {
+        let node_path = self.get_current_node_path();
+        props.insert(String::from(ID), format!("{{{{{}.sid}}}}", node_path));
+      }
+    }
+
     // 收集 wxs 标签上的 module 和 src 属性
     if is_xscript {
       let module = props.get("module");
@@ -686,18 +1141,125 @@ impl TransformVisitor {
     let mut retain_child_counter = start;
     let mut jsx_exprs_wait_for_inserting: HashMap<u32, Box<Expr>> = HashMap::new();
 
+    // 上一个有效兄弟节点是否带有 compileIf、compileElse，用于校验 compileElse 的位置
+    let mut is_prev_sibling_if = false;
+    let mut is_prev_sibling_else = false;
+
     children.retain_mut(|child| {
       let mut is_retain = true;
       self.node_stack.push(retain_child_counter as i32);
+      // 空白文本、注释不影响 compileIf 与 compileElse 的相邻关系
+      if utils::is_valid_node(child) {
+        (is_prev_sibling_if, is_prev_sibling_else) = match child {
+          JSXElementChild::JSXElement(child_el) => {
+            let is_else = utils::check_jsx_element_has_attr(child_el, COMPILE_ELSE);
+            if is_else && is_prev_sibling_else && !is_prev_sibling_if {
+              // 连续出现多个 else 分支，通常是书写失误
+              HANDLER.with(|handler| {
+                handler
+                  .struct_span_warn(child_el.span, "Taro CompileMode 提示")
+                  .span_label(
+                    child_el.span,
+                    "连续出现多个 compileElse 节点，中间缺少带有 compileIf 的兄弟节点",
+                  )
+                  .emit();
+              });
+            } else if is_else && !is_prev_sibling_if {
+              HANDLER.with(|handler| {
+                handler
+                  .struct_span_err(child_el.span, "Taro CompileMode 语法错误")
+                  .span_label(
+                    child_el.span,
+                    "compileElse 必须紧跟在带有 compileIf 的兄弟节点之后",
+                  )
+                  .emit();
+              });
+            }
+            (
+              utils::check_jsx_element_has_attr(child_el, COMPILE_IF),
+              is_else,
+            )
+          }
+          _ => (false, false),
+        };
+      }
       match child {
         JSXElementChild::JSXElement(child_el) => {
-          let child_string = self.build_xml_element(&mut **child_el);
-          children_string.push_str(&child_string);
+          // SVG 元素在小程序模板中没有对应实现，提示后从模板与 JSX 中删除
+          if utils::is_svg_element(child_el, &self.config) {
+            HANDLER.with(|handler| {
+              handler
+                .struct_span_warn(child_el.span, "Taro CompileMode 提示")
+                .span_label(
+                  child_el.span,
+                  "小程序模板不支持 SVG 元素，该节点会被忽略，请改用 Image 组件",
+                )
+                .emit();
+            });
+            self.node_stack.pop();
+            return false;
+          }
+          // 子树通过 compileMode 指定渲染策略时，跳过自动的静态分析
+          let strategy = utils::take_compile_mode_strategy(child_el);
+          if strategy.as_deref() == Some(COMPILE_MODE_DYNAMIC) {
+            // 整棵子树交给运行时渲染
+            let node_path = self.get_current_node_path();
+            let tmpl = self.generate_template(node_path, String::new());
+            children_string.push_str(&tmpl);
+            retain_child_counter += 1;
+            self.node_stack.pop();
+            return true;
+          }
+          if strategy.as_deref() == Some(COMPILE_MODE_STATIC) {
+            if utils::is_static_subtree(child_el, &self.config) {
+              // 整棵子树输出到模板，从 JSX 中删除
+              let child_string = self.build_xml_element(&mut **child_el);
+              children_string.push_str(&child_string);
+              self.node_stack.pop();
+              return false;
+            }
+            HANDLER.with(|handler| {
+              handler
+                .struct_span_err(child_el.span, "Taro CompileMode 语法错误")
+                .span_label(
+                  child_el.span,
+                  "compileMode=\"static\" 的子树中不能包含动态内容或自定义组件",
+                )
+                .emit();
+            });
+          }
 
-          if utils::is_static_jsx(child_el) && utils::is_inner_component(child_el, &self.config) {
-            is_retain = false
+          // 结构相同的纯静态子树直接复用之前生成的模板片段
+          let cache_key = if utils::is_cacheable_static_fragment(child_el, &self.config) {
+            utils::get_static_jsx_structure(child_el)
+              .map(|structure| utils::get_static_fragment_cache_key(&self.config, &structure))
           } else {
-            retain_child_counter += 1;
+            None
+          };
+          let cached_string = cache_key
+            .as_ref()
+            .and_then(|key| self.static_fragment_cache.borrow().get(key).cloned());
+          if let Some(child_string) = cached_string {
+            children_string.push_str(&child_string);
+            is_retain = false;
+          } else {
+            let child_string = self.build_xml_element(&mut **child_el);
+            children_string.push_str(&child_string);
+
+            if utils::is_static_jsx(child_el) && utils::is_inner_component(child_el, &self.config) {
+              // 只缓存不含数据绑定的模板片段
+              if let Some(key) = cache_key {
+                if !child_string.contains("{{") {
+                  self
+                    .static_fragment_cache
+                    .borrow_mut()
+                    .insert(key, child_string);
+                }
+              }
+              is_retain = false
+            } else {
+              retain_child_counter += 1;
+            }
           }
         }
         JSXElementChild::JSXExprContainer(JSXExprContainer {
@@ -743,12 +1305,112 @@ impl TransformVisitor {
               ..
             }) => {
               // 处理循环
+              let (item_param, index_param) = utils::get_loop_callback_param_names(args);
               if let Some(return_value) = utils::extract_jsx_loop(callee_expr, args) {
+                if self.config.key_as_loop_key && utils::is_default_loop_key(return_value) {
+                  if let Some(key) = utils::get_loop_key_from_key_attr(
+                    return_value,
+                    item_param.as_deref(),
+                    index_param.as_deref(),
+                  ) {
+                    utils::set_loop_key(return_value, &key);
+                  }
+                }
+                if self.config.warn_loop_without_key && utils::is_default_loop_key(return_value) {
+                  HANDLER.with(|handler| {
+                    handler
+                      .struct_span_warn(return_value.span, "Taro CompileMode 提示")
+                      .span_label(
+                        return_value.span,
+                        "循环没有指定 compileForKey，将使用默认的 sid 作为 key",
+                      )
+                      .emit();
+                  });
+                }
                 self.node_stack.pop();
                 self.node_stack.push(LOOP_WRAPPER_ID);
-                let child_string = self.build_xml_element(&mut *return_value);
+                // 嵌套循环与外层循环的 for-item 同名时，内层的 for-item 会遮蔽外层
+                // 只有两层都通过 compileForItem 指定了同一个名称，或循环体中手写的 {{...}} 引用了外层的 for-item 时才需要重命名，
+                // 其余绑定都是相对内层 for-item 的节点路径，不受影响
+                let mut item_name = utils::get_loop_item_name(return_value);
+                let is_explicit_item = utils::has_loop_item_name(return_value);
+                let is_collided = self
+                  .loop_item_names
+                  .iter()
+                  .zip(&self.loop_item_explicit)
+                  .any(|(name, &is_explicit)| {
+                    *name == item_name
+                      && ((is_explicit && is_explicit_item)
+                        || (!is_explicit_item
+                          && item_param.as_deref() != Some(name.as_str())
+                          && utils::is_loop_item_referenced(return_value, name)))
+                  });
+                if is_collided {
+                  let mut get_item_name = utils::named_iter(item_name.clone());
+                  item_name = get_item_name();
+                  while self.loop_item_names.contains(&item_name) {
+                    item_name = get_item_name();
+                  }
+                  utils::set_loop_item_name(return_value, &item_name);
+                }
+                self.loop_item_names.push(item_name);
+                self.loop_item_explicit.push(is_explicit_item);
+                let child_string = if self.config.hoist_loop_templates {
+                  self.build_hoisted_loop(&mut *return_value)
+                } else {
+                  self.build_xml_element(&mut *return_value)
+                };
+                self.loop_item_names.pop();
+                self.loop_item_explicit.pop();
                 children_string.push_str(&child_string);
-              } else if utils::is_render_fn(callee_expr) {
+              } else if let Some(callback_name) =
+                utils::get_loop_callback_ref_name(callee_expr, args)
+              {
+                HANDLER.with(|handler| {
+                  handler
+                    .struct_span_warn(callee_expr.span(), "Taro CompileMode 提示")
+                    .span_label(
+                      callee_expr.span(),
+                      &format!(
+                        "循环的回调 {} 不是内联函数，无法提取循环体，将交由运行时渲染",
+                        callback_name
+                      ),
+                    )
+                    .emit();
+                });
+                let tmpl = self.generate_template(node_path, "".to_string());
+                children_string.push_str(&tmpl)
+              } else if utils::is_call_expr_of_jsx_reduce(callee_expr, args) {
+                HANDLER.with(|handler| {
+                  handler
+                    .struct_span_err(callee_expr.span(), "Taro CompileMode 语法错误")
+                    .span_label(
+                      callee_expr.span(),
+                      "暂不支持使用 reduce 生成 JSX 列表，请改用 map",
+                    )
+                    .emit();
+                });
+                // 交由运行时渲染，避免输出错误的模板
+                let tmpl = self.generate_template(node_path, "".to_string());
+                children_string.push_str(&tmpl)
+              } else if let Some(render_fn_name) =
+                utils::get_render_fn_name(callee_expr, &self.config.render_fn_prefixes)
+              {
+                // 渲染函数不会在编译期展开，递归调用自身时提示，避免误以为会生成完整的模板
+                if self.render_fn_names.contains(&render_fn_name) {
+                  HANDLER.with(|handler| {
+                    handler
+                      .struct_span_warn(callee_expr.span(), "Taro CompileMode 提示")
+                      .span_label(
+                        callee_expr.span(),
+                        &format!(
+                          "渲染函数 {} 递归调用了自身，不会在编译期展开，将交由运行时渲染",
+                          render_fn_name
+                        ),
+                      )
+                      .emit();
+                  });
+                }
                 let tmpl = self.generate_template(node_path, "".to_string());
                 children_string.push_str(&tmpl)
               } else {
@@ -786,6 +1448,12 @@ impl TransformVisitor {
                         }),
                       },
                       _ => {
+                        // wxs 表达式作为参数时直接在模板中求值
+                        if let Some(arg_string) =
+                          utils::as_xscript_arg_string(&arg.expr, &self.xs_module_names, '"')
+                        {
+                          return arg_string;
+                        }
                         let expr = arg.expr.take();
                         jsx_exprs_wait_for_inserting.insert(retain_child_counter, expr);
                         self.node_stack.pop();
@@ -864,6 +1532,19 @@ impl TransformVisitor {
           }
           self.node_stack.push(retain_child_counter as i32);
         }
+        JSXElementChild::JSXSpreadChild(JSXSpreadChild { span, .. }) => {
+          // {...items} 无法确定展开后的节点数量，模板中没有对应写法
+          HANDLER.with(|handler| {
+            handler
+              .struct_span_err(*span, "Taro CompileMode 语法错误")
+              .span_label(
+                *span,
+                "子节点不支持展开语法 {...}，请改用 {list.map(...)} 渲染列表",
+              )
+              .emit();
+          });
+          retain_child_counter += 1;
+        }
         _ => (),
       }
       self.node_stack.pop();
@@ -892,6 +1573,57 @@ impl TransformVisitor {
     (children_string, retain_child_counter - start)
   }
 
+  // 循环体提取为具名模板，循环指令输出在外层的 block 上，通过 <template is> 引用
+  fn build_hoisted_loop(&mut self, el: &mut JSXElement) -> String {
+    let index_name = utils::get_loop_index_name(el);
+    let mut block_opening = JSXOpeningElement {
+      name: JSXElementName::Ident(quote_ident!(BLOCK_TAG)),
+      span,
+      attrs: utils::take_loop_directive_attrs(el),
+      self_closing: false,
+      type_args: None,
+    };
+    let loop_attrs = self
+      .build_xml_attrs(&mut block_opening, BLOCK_TAG)
+      .unwrap_or_default();
+    let body = self.build_xml_element(el);
+
+    // 嵌套循环的模板先于外层生成，名称按生成顺序编号
+    let hoisted_name = format!(
+      "tmpl_0_{}_l{}",
+      self.tmpl_name,
+      self.hoisted_templates.len()
+    );
+    self.hoisted_templates.push(format!(
+      r#"<template name="{}">{}</template>"#,
+      hoisted_name, body
+    ));
+
+    let item_name = self.loop_item_names.last().unwrap();
+    let xs_data = if self.config.is_use_xs { ",l:l" } else { "" };
+    let block_name = utils::convert_tag_case(
+      self
+        .config
+        .adapter
+        .get(BLOCK_TAG)
+        .map(|block| block.as_str())
+        .unwrap_or(BLOCK_TAG),
+      &self.config.tag_case,
+    );
+    format!(
+      r#"<{}{}><template is="{}" data="{{{{{}:{},{}:{},c:c{}}}}}"/></{}>"#,
+      block_name,
+      loop_attrs,
+      hoisted_name,
+      item_name,
+      item_name,
+      index_name,
+      index_name,
+      xs_data,
+      block_name
+    )
+  }
+
   fn generate_template(&mut self, node_path: String, attrs: String) -> String {
     if self.config.is_use_xs {
       format!(
@@ -912,12 +1644,15 @@ impl TransformVisitor {
 
   fn get_current_node_path(&self) -> String {
     // return: i.cn[0].cn[0]....
+    let mut loop_depth = 0;
     self
       .node_stack
       .iter()
       .fold(String::from("i"), |mut acc, item| {
         if item == &LOOP_WRAPPER_ID {
-          return String::from("item");
+          let item_name = self.get_loop_item_name(loop_depth);
+          loop_depth += 1;
+          return item_name;
         }
         acc.push_str(&format!(".cn[{}]", item));
         return acc;
@@ -926,6 +1661,7 @@ impl TransformVisitor {
 
   fn get_current_loop_path(&self) -> String {
     // return: i.cn[0]...cn
+    let mut loop_depth = 0;
     self
       .node_stack
       .iter()
@@ -935,7 +1671,9 @@ impl TransformVisitor {
           String::from(".cn")
         } else {
           if item == &LOOP_WRAPPER_ID {
-            return String::from("item");
+            let item_name = self.get_loop_item_name(loop_depth);
+            loop_depth += 1;
+            return item_name;
           }
           format!(".cn[{}]", item)
         };
@@ -944,12 +1682,36 @@ impl TransformVisitor {
       })
   }
 
+  // 第 depth 层循环的 for-item 名称，默认为 item
+  fn get_loop_item_name(&self, depth: usize) -> String {
+    self
+      .loop_item_names
+      .get(depth)
+      .cloned()
+      .unwrap_or_else(|| String::from(LOOP_ITEM))
+  }
+
   fn is_xscript_used(&self) -> bool {
     return self.xs_module_names.len() > 0;
   }
 
+  /**
+   * 遍历渲染函数的函数体时记录函数名，其余函数直接遍历
+   */
+  fn visit_mut_render_fn(&mut self, name: String, visit: impl FnOnce(&mut Self)) {
+    if !utils::is_render_fn_name(&name, &self.config.render_fn_prefixes)
+      || !self.render_fn_names.insert(name.clone())
+    {
+      return visit(self);
+    }
+    visit(self);
+    self.render_fn_names.remove(&name);
+  }
+
   fn reset_states(&mut self) -> () {
     self.xs_module_names = vec![];
+    self.hoisted_templates = vec![];
+    self.inline_xs_fns = vec![];
   }
 }
 
@@ -974,17 +1736,49 @@ impl VisitMut for TransformVisitor {
 
     if self.is_compile_mode {
       self.reset_states();
-      transform_taro_components(el, &self.import_specifiers, &self.import_aliases);
+      let removed_attrs = transform_taro_components(
+        el,
+        &self.import_specifiers,
+        &self.import_aliases,
+        &self.config.extra_scroll_view_props,
+        &self.config.platform,
+      );
+      for (attr_span, label) in removed_attrs {
+        HANDLER.with(|handler| {
+          handler
+            .struct_span_warn(attr_span, "Taro CompileMode 提示")
+            .span_label(attr_span, &label)
+            .emit();
+        });
+      }
+      utils::inject_component_default_attrs(el, &self.config.component_default_attrs);
+      utils::transform_class_array_attrs(&mut el.opening.attrs);
+      utils::transform_class_names_call_attrs(&mut el.opening.attrs);
+      utils::remove_empty_class_style_attrs(&mut el.opening.attrs);
+      utils::transform_inner_html(el);
       el.visit_mut_children_with(&mut PreVisitor::new(
         self.import_specifiers.clone(),
         self.import_aliases.clone(),
+        self.config.component_default_attrs.clone(),
+        self.config.extra_scroll_view_props.clone(),
+        self.config.platform.clone(),
       ));
 
+      self.tmpl_name = tmpl_name.clone();
+      let mut contents = self.build_xml_element(el);
+      if !self.inline_xs_fns.is_empty() {
+        contents = format!(
+          "{}{}",
+          utils::create_inline_xs_module(&self.inline_xs_fns, &self.config.adapter),
+          contents
+        );
+      }
       let tmpl_contents = format!(
-        r#"{}<template name="tmpl_0_{}">{}</template>{}"#,
+        r#"{}<template name="tmpl_0_{}">{}</template>{}{}"#,
         self.config.template_tag.clone(),
         &tmpl_name,
-        self.build_xml_element(el),
+        contents,
+        self.hoisted_templates.concat(),
         self.config.template_tag.clone()
       );
       self.templates.insert(tmpl_name, tmpl_contents);
@@ -994,6 +1788,48 @@ impl VisitMut for TransformVisitor {
     }
   }
 
+  fn visit_mut_fn_decl(&mut self, fn_decl: &mut FnDecl) {
+    let name = fn_decl.ident.sym.to_string();
+    self.visit_mut_render_fn(name, |visitor| fn_decl.visit_mut_children_with(visitor));
+  }
+
+  fn visit_mut_var_declarator(&mut self, declarator: &mut VarDeclarator) {
+    if let (Pat::Ident(BindingIdent { id, .. }), Some(init)) =
+      (&declarator.name, &mut declarator.init)
+    {
+      // React.memo、forwardRef 包裹的组件，直接遍历内层的函数
+      let func = utils::unwrap_component_wrapper(init);
+      if func.is_arrow() || func.is_fn_expr() {
+        let name = id.sym.to_string();
+        self.visit_mut_render_fn(name, |visitor| func.visit_mut_with(visitor));
+        return;
+      }
+    }
+    declarator.visit_mut_children_with(self);
+  }
+
+  fn visit_mut_class_method(&mut self, method: &mut ClassMethod) {
+    if let PropName::Ident(Ident { sym, .. }) = &method.key {
+      let name = sym.to_string();
+      self.visit_mut_render_fn(name, |visitor| method.visit_mut_children_with(visitor));
+      return;
+    }
+    method.visit_mut_children_with(self);
+  }
+
+  fn visit_mut_expr(&mut self, expr: &mut Expr) {
+    // 已经编译为 React.createElement 的代码，先转换为 JSX 再按 JSX 处理
+    if self.config.transform_create_element
+      && !self.is_compile_mode
+      && utils::is_compile_mode_create_element(expr)
+    {
+      if let Some(el) = utils::create_element_to_jsx(expr) {
+        *expr = Expr::JSXElement(Box::new(el));
+      }
+    }
+    expr.visit_mut_children_with(self);
+  }
+
   fn visit_mut_module_items(&mut self, body_stmts: &mut Vec<ModuleItem>) {
     // 收集模块导入信息
     self.collect_import_info(body_stmts);
@@ -1,8 +1,13 @@
-use crate::utils::{self, constants::*, harmony::components::*};
+use crate::utils::{
+  self,
+  constants::*,
+  harmony::{components::*, node_path::NodePath},
+};
 use crate::{ComponentReplace, PluginConfig};
 use regex::Regex;
 use std::collections::HashMap;
 use std::collections::HashSet;
+use std::fmt;
 use swc_core::{
   common::{util::take::Take, DUMMY_SP as span},
   ecma::{
@@ -25,6 +30,18 @@ impl PreVisitor {
     Self {}
   }
   fn process_cond_expr(&self, expr: &mut CondExpr) {
+    // C?'A':<B /> 的文本分支上无法挂载 compileIf，对条件取反并交换两个分支，由元素分支承载 compileIf
+    if utils::is_text_expr(&expr.cons) && expr.alt.is_jsx_element() {
+      expr.test = Box::new(Expr::Unary(UnaryExpr {
+        span,
+        op: op!("!"),
+        arg: Box::new(Expr::Paren(ParenExpr {
+          span,
+          expr: expr.test.take(),
+        })),
+      }));
+      std::mem::swap(&mut expr.cons, &mut expr.alt);
+    }
     let test = &expr.test;
     let cons = &mut expr.cons;
     let compile_if = utils::create_jsx_expr_attr(COMPILE_IF, test.clone());
@@ -41,6 +58,17 @@ impl PreVisitor {
   }
 }
 impl VisitMut for PreVisitor {
+  fn visit_mut_jsx_element_children(&mut self, children: &mut Vec<JSXElementChild>) {
+    utils::expand_jsx_array_children(children);
+    children.visit_mut_children_with(self);
+  }
+  fn visit_mut_jsx_element(&mut self, el: &mut JSXElement) {
+    // compileIgnore 的子树不会输出到模板，跳过整棵子树
+    if utils::check_jsx_element_has_compile_ignore(el) {
+      return;
+    }
+    el.visit_mut_children_with(self);
+  }
   fn visit_mut_jsx_element_child(&mut self, child: &mut JSXElementChild) {
     if let JSXElementChild::JSXExprContainer(JSXExprContainer {
       expr: JSXExpr::Expr(expr),
@@ -129,12 +157,17 @@ pub struct TransformVisitor {
   pub templates: HashMap<String, String>,
   pub get_tmpl_name: Box<dyn FnMut() -> String>,
   pub node_name_vec: Vec<String>,
+  // 当前编译的节点中，动态 id 与节点路径的映射：HashMap<动态 id, 节点路径>
+  pub node_paths: HashMap<String, String>,
+  // HashMap<模板名, HashMap<动态 id, 节点路径>>
+  pub node_path_maps: HashMap<String, HashMap<String, String>>,
   pub get_node_name: Box<dyn FnMut() -> String>,
   pub deal_loop_now: bool,
 }
 
 impl TransformVisitor {
-  pub fn new(config: PluginConfig) -> Self {
+  pub fn new(mut config: PluginConfig) -> Self {
+    utils::normalize_component_names(&mut config.components);
     let get_node_name = Box::new(utils::named_iter(String::from("node")));
     let get_tmpl_name = Box::new(utils::named_iter(format!("{}t", config.tmpl_prefix)));
 
@@ -147,18 +180,43 @@ impl TransformVisitor {
       get_tmpl_name,
       component_set: HashSet::new(),
       node_name_vec: vec![],
+      node_paths: HashMap::new(),
+      node_path_maps: HashMap::new(),
       get_node_name,
       deal_loop_now: false,
     }
   }
 
-  pub fn get_dynmaic_node_name(&mut self, name: String) -> String {
-    let node_name = if self.deal_loop_now {
+  pub fn get_dynamic_node_name(&mut self, name: impl ToString) -> String {
+    let name = name.to_string();
+    if self.deal_loop_now {
       name
     } else {
       format!("this.{}", name)
-    };
-    node_name.to_string()
+    }
+  }
+
+  /**
+   * 把已生成的模板按模板名顺序逐个写入 sink，适合输出体积较大的场景
+   */
+  pub fn write_templates(&self, sink: &mut impl fmt::Write) -> fmt::Result {
+    utils::write_templates(&self.templates, sink)
+  }
+
+  /**
+   * 获取编译后组件中动态 id 到节点路径的映射，供运行时查找动态节点，可直接序列化为 JSON
+   */
+  pub fn get_node_path_map(&self, tmpl_name: &str) -> HashMap<String, String> {
+    self
+      .node_path_maps
+      .get(tmpl_name)
+      .cloned()
+      .unwrap_or_default()
+  }
+
+  #[deprecated(note = "please use `get_dynamic_node_name` instead")]
+  pub fn get_dynmaic_node_name(&mut self, name: impl ToString) -> String {
+    self.get_dynamic_node_name(name)
   }
 
   fn build_ets_element(&mut self, el: &mut JSXElement) -> String {
@@ -194,10 +252,7 @@ impl TransformVisitor {
             let element_direction: EtsDirection = self.build_ets_direction(opening_element);
             let mut children = utils::create_original_node_renderer_foreach(self);
 
-            // 只处理元素的子元素只有一个循环的情况和子元素没有循环的情况，其他情况先用 createLazyChildren 生成子结点
-            let is_loop_exist = utils::check_jsx_element_children_exist_loop(el);
-            let el_children_len = utils::get_valid_nodes(&el.children);
-            if !is_loop_exist || (is_loop_exist && el_children_len == 1) {
+            if !utils::should_render_children_lazily(el, &self.config) {
               let (temp_children, ..) = self.build_ets_children(&mut el.children, None);
               children = temp_children;
             }
@@ -207,7 +262,7 @@ impl TransformVisitor {
               self.node_name.pop();
             }
 
-            let current_node_name = self.get_dynmaic_node_name(dynmaic_node_name);
+            let current_node_name = self.get_dynamic_node_name(dynmaic_node_name);
             // 如果config配置的替换组件里有这个，就直接拿配置项里的当组件实例化
             let mut code = if self.config.component_replace.contains_key(name.as_str()) {
               self.component_set.insert(name.clone());
@@ -327,7 +382,7 @@ impl TransformVisitor {
               }
               if !handle_loop {
                 let mut tmpl = utils::create_normal_text_template(self, false);
-                if utils::is_render_fn(callee_expr) {
+                if utils::is_render_fn(callee_expr, &self.config.render_fn_prefixes) {
                   tmpl = utils::create_original_node_renderer(self);
                 }
                 children_string.push_str(&tmpl);
@@ -343,9 +398,9 @@ impl TransformVisitor {
         JSXElementChild::JSXText(jsx_text) => {
           let content = utils::jsx_text_to_string(&jsx_text.value);
           if !content.is_empty() {
-            let current_path = self.get_current_node_path();
+            let current_path = self.current_node_path();
             let code = utils::add_spaces_to_lines(
-              get_text_component_str(&self.get_dynmaic_node_name(current_path)).as_str(),
+              get_text_component_str(&self.get_dynamic_node_name(current_path)).as_str(),
             );
 
             children_string.push_str(code.as_str());
@@ -373,6 +428,10 @@ impl TransformVisitor {
   }
 
   fn build_ets_cond_expr(&mut self, cond_expr: &mut CondExpr) -> String {
+    // C?'A':'B' 的两个分支对应同一个文本节点，运行时已按条件更新文本内容，只输出一次该文本节点
+    if utils::is_text_expr(&cond_expr.cons) && utils::is_text_expr(&cond_expr.alt) {
+      return utils::create_normal_text_template(self, false);
+    }
     let mut children_string = String::new();
     let mut process_condition_expr = |arm: &mut Box<Expr>| {
       match &mut **arm {
@@ -384,16 +443,9 @@ impl TransformVisitor {
             self.build_ets_element(el)
           }
         }
-        Expr::Lit(_) => {
-          // {condition1 && 'Hello'} 在预处理时会变成 {condition1 ? 'Hello' : "compileIgnore"}
-          // 而普通文本三元则会被 block 标签包裹，因此处理后只有上述情况会存在 lit 类型的表达式
-          // 由于这种情况没有办法使用 wx:if 来处理，需要特殊处理成 {{i.cn[3].v==="compileIgnore"?"":i.cn[3].v}} 的形式
-          let current_path = self.get_current_node_path();
-
-          self.component_set.insert(TEXT_TAG.to_string());
-          utils::add_spaces_to_lines(
-            get_text_component_str(&self.get_dynmaic_node_name(current_path)).as_str(),
-          )
+        // C?<A />:'B' 的文本分支，由元素分支上的 compileIf 决定渲染哪个分支
+        Expr::Lit(_) | Expr::Ident(_) | Expr::Member(_) | Expr::Tpl(_) => {
+          utils::create_normal_text_template(self, false)
         }
         Expr::Cond(cond_expr) => self.build_ets_cond_expr(cond_expr),
         _ => String::new(),
@@ -405,7 +457,7 @@ impl TransformVisitor {
     children_string.push_str(
       format!(
         "if (({} as TaroElement)._attrs.compileIf) {{\n{}}}",
-        self.get_dynmaic_node_name(self.get_current_node_path()),
+        self.get_dynamic_node_name(self.current_node_path()),
         cons_children_string
       )
       .as_str(),
@@ -425,7 +477,11 @@ impl TransformVisitor {
         if let JSXAttrName::Ident(..) = &jsx_attr.name {
           if let JSXAttrName::Ident(Ident { sym: name, .. }) = &jsx_attr.name {
             let jsx_attr_name = name.to_string();
-            let event_name = utils::identify_jsx_event_key(&jsx_attr_name, &self.config.platform);
+            let event_name = utils::identify_jsx_event_key(
+              &jsx_attr_name,
+              &self.config.platform,
+              utils::get_event_prefix(&self.config.adapter),
+            );
             let is_event = event_name.is_some();
             let is_condition = jsx_attr_name == COMPILE_IF;
 
@@ -552,7 +608,7 @@ impl TransformVisitor {
           event_string.push_str(&create_component_event(
             jsx_attr_name.as_str(),
             self
-              .get_dynmaic_node_name(self.get_current_node_path())
+              .get_dynamic_node_name(self.current_node_path())
               .as_str(),
           ));
         }
@@ -562,22 +618,17 @@ impl TransformVisitor {
     event_string
   }
 
-  pub fn get_current_node_path(&self) -> String {
-    let current_node_name = self.node_name.last().unwrap().clone();
-    let current_node_stack = match self.node_stack.get(&current_node_name) {
-      Some(stack) => stack,
-      None => {
-        return current_node_name;
-      }
-    };
+  pub fn current_node_path(&self) -> NodePath {
+    let current_node_name = self.node_name.last().unwrap();
+    match self.node_stack.get(current_node_name) {
+      Some(stack) => NodePath::from_indexes(current_node_name, stack),
+      None => NodePath::new(current_node_name),
+    }
+  }
 
+  pub fn get_current_node_path(&self) -> String {
     // return: node0.childNodes[0].childNodes[0]....
-    current_node_stack
-      .iter()
-      .fold(current_node_name, |mut acc, item| {
-        acc.push_str(&format!(".childNodes[{}]", item));
-        return acc;
-      })
+    self.current_node_path().to_string()
   }
 
   pub fn pop_node_stack(&mut self) {
@@ -670,7 +721,15 @@ export default struct TARO_TEMPLATES_{name} {{
         )
         .as_str()
         + utils::get_harmony_component_style(self).as_str();
+      let tmpl_contents = if self.config.minify {
+        utils::minify_lines(&tmpl_contents)
+      } else {
+        tmpl_contents
+      };
 
+      self
+        .node_path_maps
+        .insert(tmpl_name.clone(), self.node_paths.clone());
       self
         .templates
         .insert(tmpl_name, format!("`{}`", tmpl_contents));
@@ -681,6 +740,7 @@ export default struct TARO_TEMPLATES_{name} {{
       self.node_stack.clear();
       self.node_name.clear();
       self.node_name_vec.clear();
+      self.node_paths.clear();
       self.component_set.clear();
       self.is_compile_mode = false;
       self.get_node_name = Box::new(utils::named_iter(String::from("node")));
@@ -1,11 +1,27 @@
 pub const COMPILE_MODE: &str = "compileMode";
+// 子树上的 compileMode 渲染策略
+pub const COMPILE_MODE_STATIC: &str = "static";
+pub const COMPILE_MODE_DYNAMIC: &str = "dynamic";
+pub const COMPILE_MODE_AUTO: &str = "auto";
 pub const COMPILE_IF: &str = "compileIf";
 pub const COMPILE_ELSE: &str = "compileElse";
 pub const COMPILE_IGNORE: &str = "compileIgnore";
 pub const COMPILE_FOR: &str = "compileFor";
 pub const COMPILE_FOR_KEY: &str = "compileForKey";
+pub const COMPILE_FOR_ITEM: &str = "compileForItem";
+pub const COMPILE_FOR_INDEX: &str = "compileForIndex";
+// 标记需要被 IntersectionObserver 观察的节点，如懒加载列表的列表项
+pub const COMPILE_OBSERVE: &str = "compileObserve";
+pub const OBSERVE_CLASS: &str = "taro-observe";
+pub const LOOP_ITEM: &str = "item";
+pub const LOOP_INDEX: &str = "index";
+pub const COMPILE_RAW_NS: &str = "raw";
+pub const MARK_NS: &str = "mark";
 pub const SLOT_ITEM: &str = "slotItem";
+pub const SLOT_ATTR: &str = "slot";
 pub const EVENT_HANDLER: &str = "eh";
+// 事件绑定的默认前缀，可以通过 adapter 的 eventPrefix 配置，如 bind:
+pub const DEFAULT_EVENT_PREFIX: &str = "bind";
 pub const DATA_SID: &str = "data-sid";
 pub const TMPL_DATA_ROOT: &str = "i.";
 pub const ID: &str = "id";
@@ -14,9 +30,222 @@ pub const DYNAMIC_ID: &str = "_dynamicID";
 pub const REACT_RESERVED: [&str; 2] = ["key", "ref"];
 
 pub const VIEW_TAG: &str = "view";
+pub const BLOCK_TAG: &str = "block";
+pub const PAGE_META_TAG: &str = "page-meta";
+pub const NAVIGATION_BAR_TAG: &str = "navigation-bar";
+pub const CUSTOM_WRAPPER_TAG: &str = "custom-wrapper";
+pub const PICKER_TAG: &str = "picker";
+pub const CANVAS_TAG: &str = "canvas";
+pub const RICH_TEXT_TAG: &str = "rich-text";
+pub const VIDEO_TAG: &str = "video";
+pub const SWIPER_ITEM_TAG: &str = "swiper-item";
+pub const REFRESHER_SLOT: &str = "refresher";
+pub const SUSPENSE_TAG: &str = "Suspense";
+// 拼接类名的工具函数，如 classNames('a', { b: cond })
+pub const CLASS_NAMES_CALLEES: [&str; 4] = ["classNames", "classnames", "cx", "clsx"];
+pub const DANGEROUSLY_SET_INNER_HTML: &str = "dangerouslySetInnerHTML";
 pub const TEXT_TAG: &str = "text";
 pub const IMAGE_TAG: &str = "image";
 pub const SCRIPT_TAG: &str = "script";
+pub const BUTTON_TAG: &str = "button";
+pub const FORM_CONTROL_TAGS: [&str; 2] = ["input", "textarea"];
+pub const FORM_FOCUS_EVENTS: [&str; 2] = ["onFocus", "onBlur"];
+pub const MOVABLE_TAGS: [&str; 2] = ["movable-view", "movable-area"];
+pub const MOVABLE_PASSTHROUGH_ATTRS: [&str; 2] = ["catch-move", "disable-scroll"];
+// 只有部分平台支持的事件：(事件名, 支持的平台)
+pub const PLATFORM_LIMITED_EVENTS: [(&str, &[&str]); 5] = [
+  // touchforcechange（3D Touch）
+  ("onTouchForceChange", &["WEAPP", "QQ"]),
+  // CSS 动画、过渡事件
+  ("onTransitionEnd", &["WEAPP", "QQ", "ALIPAY", "TT"]),
+  ("onAnimationStart", &["WEAPP", "QQ", "ALIPAY", "TT"]),
+  ("onAnimationIteration", &["WEAPP", "QQ", "ALIPAY", "TT"]),
+  ("onAnimationEnd", &["WEAPP", "QQ", "ALIPAY", "TT"]),
+];
+// 各平台 Button 支持的 open-type：(平台, 取值)
+pub const BUTTON_OPEN_TYPES: [(&str, &[&str]); 5] = [
+  (
+    "WEAPP",
+    &[
+      "contact",
+      "liveActivity",
+      "share",
+      "getPhoneNumber",
+      "getRealtimePhoneNumber",
+      "getUserInfo",
+      "launchApp",
+      "openSetting",
+      "feedback",
+      "chooseAvatar",
+      "agreePrivacyAuthorization",
+    ],
+  ),
+  (
+    "ALIPAY",
+    &[
+      "share",
+      "getAuthorize",
+      "contactShare",
+      "lifestyle",
+      "launchApp",
+      "getPhoneNumber",
+      "chooseAvatar",
+    ],
+  ),
+  (
+    "SWAN",
+    &[
+      "contact",
+      "share",
+      "getUserInfo",
+      "getPhoneNumber",
+      "openSetting",
+      "chooseAddress",
+      "chooseInvoiceTitle",
+      "login",
+      "subscribe",
+      "chooseAvatar",
+    ],
+  ),
+  (
+    "TT",
+    &[
+      "share",
+      "getPhoneNumber",
+      "contact",
+      "openSetting",
+      "chooseAvatar",
+      "im",
+    ],
+  ),
+  (
+    "QQ",
+    &[
+      "share",
+      "getUserInfo",
+      "launchApp",
+      "openSetting",
+      "feedback",
+      "getPhoneNumber",
+      "openGroupProfile",
+      "addFriend",
+      "addColorSign",
+      "openPublicProfile",
+      "addGroupApp",
+      "shareMessageToFriend",
+      "addToFavorites",
+    ],
+  ),
+];
+// UnitPolicy 转换 px 尺寸的属性，除 style 类属性外只有这些属性的取值是尺寸
+pub const UNIT_SIZE_ATTRS: [&str; 4] = ["width", "height", "previous-margin", "next-margin"];
+// 可以识别的全小写事件名：(全小写写法, 对应的 onXxx 事件名)
+pub const LOWERCASE_EVENTS: [(&str, &str); 12] = [
+  ("onclick", "onClick"),
+  ("ontap", "onTap"),
+  ("onlongpress", "onLongPress"),
+  ("ontouchstart", "onTouchStart"),
+  ("ontouchmove", "onTouchMove"),
+  ("ontouchend", "onTouchEnd"),
+  ("ontouchcancel", "onTouchCancel"),
+  ("oninput", "onInput"),
+  ("onchange", "onChange"),
+  ("onfocus", "onFocus"),
+  ("onblur", "onBlur"),
+  ("onscroll", "onScroll"),
+];
+// 模板绑定中不支持可选链 a?.b 的平台
+pub const PLATFORMS_WITHOUT_OPTIONAL_CHAINING: [&str; 5] = ["WEAPP", "ALIPAY", "SWAN", "TT", "QQ"];
+// 模板绑定中不支持空值合并 a ?? b 的平台
+pub const PLATFORMS_WITHOUT_NULLISH_COALESCING: [&str; 5] = ["WEAPP", "ALIPAY", "SWAN", "TT", "QQ"];
+// 支持在模板中内联 wxs 模块源码的平台，以及 inline_simple_exprs 生成的内联模块名
+pub const INLINE_XS_PLATFORMS: [&str; 2] = ["WEAPP", "QQ"];
+pub const INLINE_XS_MODULE: &str = "ixs";
+// 各平台模板中的保留属性名（kebab-case 之后），普通属性与其冲突时会被当作模板指令解析
+pub const PLATFORM_RESERVED_ATTRS: [(&str, &[&str]); 1] = [(
+  "SWAN",
+  &[
+    "s-if",
+    "s-elif",
+    "s-else",
+    "s-for",
+    "s-for-index",
+    "s-for-item",
+    "s-key",
+  ],
+)];
+// Picker 各模式专有的属性
+pub const PICKER_MODE_ATTRS: [&str; 7] = [
+  "range",
+  "rangeKey",
+  "start",
+  "end",
+  "fields",
+  "customItem",
+  "level",
+];
+pub const VIDEO_ATTRS: [&str; 36] = [
+  "src",
+  "duration",
+  "controls",
+  "danmuList",
+  "danmuBtn",
+  "enableDanmu",
+  "autoplay",
+  "loop",
+  "muted",
+  "initialTime",
+  "direction",
+  "showProgress",
+  "showFullscreenBtn",
+  "showPlayBtn",
+  "showCenterPlayBtn",
+  "enableProgressGesture",
+  "objectFit",
+  "poster",
+  "showMuteBtn",
+  "title",
+  "playBtnPosition",
+  "enablePlayGesture",
+  "autoPauseIfNavigate",
+  "autoPauseIfOpenNative",
+  "vslideGesture",
+  "vslideGestureInFullscreen",
+  "showBottomProgress",
+  "pictureInPictureMode",
+  "pictureInPictureShowProgress",
+  "enableAutoRotation",
+  "showScreenLockButton",
+  "showSnapshotButton",
+  "showCastingButton",
+  "adUnitId",
+  "posterForCrawler",
+  "referrerPolicy",
+];
+// 没有子节点时以自闭合形式输出的组件
+pub const VOID_TAGS: [&str; 2] = ["image", "input"];
+// 包裹组件的 React API，如 React.memo(Comp)、forwardRef((props, ref) => ...)
+pub const COMPONENT_WRAPPERS: [&str; 2] = ["memo", "forwardRef"];
+// scroll-view 的 upperThreshold、lowerThreshold 需要带上 px 单位的平台
+pub const SCROLL_THRESHOLD_PX_PLATFORMS: [&str; 1] = ["SWAN"];
+pub const SCROLL_THRESHOLD_ATTRS: [&str; 2] = ["upperThreshold", "lowerThreshold"];
+// 小程序模板中没有对应实现的 SVG 元素
+pub const SVG_TAGS: [&str; 14] = [
+  "svg",
+  "path",
+  "g",
+  "circle",
+  "rect",
+  "line",
+  "polyline",
+  "polygon",
+  "ellipse",
+  "defs",
+  "use",
+  "linearGradient",
+  "radialGradient",
+  "stop",
+];
 
 pub const STYLE_ATTR: &str = "style";
 pub const DIRECTION_ATTR: &str = "harmonyDirection";
@@ -108,6 +337,18 @@ function getTextInViewWidth (node: TaroElement | null): TaroAny {
 
 "#;
 
+pub const HARMONY_IMAGE_STYLE_BIND: &str = r#"
+@Extend(Image)
+function imageStyleBind (node: TaroElement) {
+  .borderRadius({
+    topLeft: node._st.hmStyle.borderTopLeftRadius,
+    topRight: node._st.hmStyle.borderTopRightRadius,
+    bottomLeft: node._st.hmStyle.borderBottomLeftRadius,
+    bottomRight: node._st.hmStyle.borderBottomRightRadius
+  })
+}
+"#;
+
 pub const HARMONY_TEXT_BUILDER: &str = r#"@Builder
 function createText (node: TaroTextElement) {
   if (node.nodeType === NodeType.TEXT_NODE) {
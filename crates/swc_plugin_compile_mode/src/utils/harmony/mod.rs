@@ -1 +1,2 @@
 pub mod components;
+pub mod node_path;
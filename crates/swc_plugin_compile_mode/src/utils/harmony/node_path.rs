@@ -0,0 +1,71 @@
+use std::fmt;
+
+// 鸿蒙模板中节点的访问路径，只在输出模板时才渲染成 node0.childNodes[0].childNodes[1] 的形式
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NodePath {
+  root: String,
+  indexes: Vec<i32>,
+}
+
+impl NodePath {
+  pub fn new(root: &str) -> Self {
+    Self::from_indexes(root, &[])
+  }
+
+  pub fn from_indexes(root: &str, indexes: &[i32]) -> Self {
+    Self {
+      root: root.to_string(),
+      indexes: indexes.to_vec(),
+    }
+  }
+
+  pub fn push(&mut self, index: i32) {
+    self.indexes.push(index);
+  }
+
+  pub fn pop(&mut self) -> Option<i32> {
+    self.indexes.pop()
+  }
+
+  pub fn root(&self) -> &str {
+    &self.root
+  }
+
+  pub fn indexes(&self) -> &[i32] {
+    &self.indexes
+  }
+
+  pub fn is_root(&self) -> bool {
+    self.indexes.is_empty()
+  }
+}
+
+impl fmt::Display for NodePath {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", self.root)?;
+    for index in &self.indexes {
+      write!(f, ".childNodes[{}]", index)?;
+    }
+    Ok(())
+  }
+}
+
+#[test]
+fn test_node_path() {
+  let mut path = NodePath::new("node0");
+  assert!(path.is_root());
+  assert_eq!("node0", path.to_string());
+
+  path.push(1);
+  path.push(0);
+  assert_eq!("node0.childNodes[1].childNodes[0]", path.to_string());
+  assert_eq!(&[1, 0], path.indexes());
+
+  assert_eq!(Some(0), path.pop());
+  assert_eq!("node0.childNodes[1]", path.to_string());
+  assert_eq!(NodePath::from_indexes("node0", &[1]), path);
+
+  path.pop();
+  assert_eq!(None, path.pop());
+  assert_eq!("node0", path.root());
+}
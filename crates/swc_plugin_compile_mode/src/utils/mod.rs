@@ -1,7 +1,10 @@
 use regex::Regex;
-use std::collections::{HashMap, HashSet};
+use std::collections::{hash_map::DefaultHasher, HashMap, HashSet};
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::sync::OnceLock;
 use swc_core::{
-  common::{iter::IdentifyLast, util::take::Take, DUMMY_SP as span},
+  common::{iter::IdentifyLast, util::take::Take, Span, DUMMY_SP as span},
   ecma::{
     ast::*,
     atoms::Atom,
@@ -10,9 +13,12 @@ use swc_core::{
   },
 };
 
-use self::{constants::*, harmony::components::get_text_component_str};
-use crate::PluginConfig;
+use self::{
+  constants::*,
+  harmony::{components::get_text_component_str, node_path::NodePath},
+};
 use crate::{transform_harmony::TransformVisitor, ComponentReplace};
+use crate::{PluginConfig, TagCase, UnitPolicy};
 
 pub mod constants;
 pub mod harmony;
@@ -60,19 +66,55 @@ pub fn to_kebab_case(val: &str) -> String {
   res
 }
 
+/**
+ * 各平台模板语法的默认配置，用户未在 adapter 中配置的项以此补全
+ */
+pub fn default_adapter(platform: &str) -> HashMap<String, String> {
+  let (prefix, xs, platform_type) = match platform {
+    "WEAPP" => ("wx:", "wxs", "weapp"),
+    "ALIPAY" => ("a:", "sjs", "alipay"),
+    "SWAN" => ("s-", "sjs", "swan"),
+    "TT" => ("tt:", "sjs", "tt"),
+    "QQ" => ("qq:", "qs", "qq"),
+    _ => return HashMap::new(),
+  };
+  let mut adapter: HashMap<String, String> = [
+    ("if", "if"),
+    ("else", "else"),
+    ("elseif", "elif"),
+    ("for", "for"),
+    ("forItem", "for-item"),
+    ("forIndex", "for-index"),
+    ("key", "key"),
+  ]
+  .iter()
+  .map(|(key, directive)| (key.to_string(), format!("{}{}", prefix, directive)))
+  .collect();
+  adapter.insert(String::from("xs"), String::from(xs));
+  adapter.insert(String::from("type"), String::from(platform_type));
+  adapter
+}
+
 pub fn convert_jsx_attr_key(jsx_key: &str, adapter: &HashMap<String, String>) -> String {
   if jsx_key == "className" {
     return String::from("class");
+  } else if jsx_key == SLOT_ATTR {
+    // 具名插槽 slot 原样保留
+    return String::from(jsx_key);
   } else if jsx_key == COMPILE_IF
     || jsx_key == COMPILE_ELSE
     || jsx_key == COMPILE_FOR
     || jsx_key == COMPILE_FOR_KEY
+    || jsx_key == COMPILE_FOR_ITEM
+    || jsx_key == COMPILE_FOR_INDEX
   {
     let expr = match jsx_key {
       COMPILE_IF => "if",
       COMPILE_ELSE => "else",
       COMPILE_FOR => "for",
       COMPILE_FOR_KEY => "key",
+      COMPILE_FOR_ITEM => "forItem",
+      COMPILE_FOR_INDEX => "forIndex",
       _ => "",
     };
     let adapter = adapter
@@ -83,11 +125,319 @@ pub fn convert_jsx_attr_key(jsx_key: &str, adapter: &HashMap<String, String>) ->
   to_kebab_case(jsx_key)
 }
 
+/**
+ * Input/Textarea 的受控与非受控写法：
+ * onChange 与 onInput 一样绑定到 bindinput，defaultValue 作为 value 的初始值输出
+ */
+pub fn convert_form_control_attr_key<'a>(element_name: &str, jsx_key: &'a str) -> &'a str {
+  if FORM_CONTROL_TAGS.contains(&element_name) {
+    match jsx_key {
+      "onChange" => return "onInput",
+      "defaultValue" => return "value",
+      _ => (),
+    }
+  }
+  jsx_key
+}
+
+/**
+ * Input/Textarea 的 onFocus、onBlur：支付宝模板直接使用 onFocus、onBlur，其余平台为 bindfocus、bindblur
+ */
+pub fn convert_form_control_focus_event(
+  element_name: &str,
+  jsx_key: &str,
+  platform: &str,
+  event_prefix: &str,
+) -> Option<String> {
+  if !FORM_CONTROL_TAGS.contains(&element_name) || !FORM_FOCUS_EVENTS.contains(&jsx_key) {
+    return None;
+  }
+  match platform {
+    "ALIPAY" => Some(jsx_key.to_string()),
+    _ => Some(format!("{}{}", event_prefix, jsx_key[2..].to_lowercase())),
+  }
+}
+
+/**
+ * identify: `catchTap`、`catch:tap` 等直接书写的 catch 事件
+ * 返回 (运行时使用的 onXxx 事件名, 当前平台的 catch 事件绑定名)
+ * 如：catchTouchMove -> (onTouchMove, catchtouchmove)，支付宝 -> (onTouchMove, catchTouchMove)
+ */
+pub fn identify_jsx_catch_event_key(
+  name: &JSXAttrName,
+  platform: &str,
+) -> Option<(String, String)> {
+  let event_name = match name {
+    JSXAttrName::Ident(Ident { sym, .. }) => {
+      let event_name = sym.strip_prefix("catch")?;
+      // catchMove 是 Taro 组件的属性而不是事件
+      if !event_name.starts_with(|c: char| c.is_uppercase()) || event_name == "Move" {
+        return None;
+      }
+      event_name.to_string()
+    }
+    JSXAttrName::JSXNamespacedName(JSXNamespacedName { ns, name }) if ns.sym == "catch" => {
+      name.sym.to_string()
+    }
+    _ => return None,
+  };
+  let event_name = if event_name.to_lowercase() == "click" {
+    String::from("tap")
+  } else {
+    event_name
+  };
+  // catch:touchmove 等全小写写法按 LOWERCASE_EVENTS 还原为 onTouchMove
+  let jsx_event_name = normalize_lowercase_event_key(&format!("on{}", event_name.to_lowercase()))
+    .map(String::from)
+    .unwrap_or_else(|| format!("on{}{}", event_name[..1].to_uppercase(), &event_name[1..]));
+  let catch_event_name = match platform {
+    "ALIPAY" => format!("catch{}", &jsx_event_name[2..]),
+    _ => format!("catch{}", jsx_event_name[2..].to_lowercase()),
+  };
+  Some((jsx_event_name, catch_event_name))
+}
+
+/**
+ * identify: 迁移代码中直接书写的 `bindtap`、`bind:tap` 等小程序事件
+ * 返回 (运行时使用的 onXxx 事件名, 原样输出的事件绑定名)，如：bindtouchstart -> (onTouchstart, bindtouchstart)
+ */
+pub fn identify_jsx_bind_event_key(name: &JSXAttrName) -> Option<(String, String)> {
+  let (event_name, bind_event_name) = match name {
+    JSXAttrName::Ident(Ident { sym, .. }) => {
+      let event_name = sym.strip_prefix("bind")?;
+      // 小程序的事件绑定名全部为小写字母
+      if event_name.is_empty() || !event_name.chars().all(|c| c.is_ascii_lowercase()) {
+        return None;
+      }
+      (event_name.to_string(), sym.to_string())
+    }
+    JSXAttrName::JSXNamespacedName(JSXNamespacedName { ns, name }) if ns.sym == "bind" => {
+      (name.sym.to_string(), format!("{}:{}", ns.sym, name.sym))
+    }
+    _ => return None,
+  };
+  let capitalized_name = format!("{}{}", event_name[..1].to_uppercase(), &event_name[1..]);
+  Some((format!("on{}", capitalized_name), bind_event_name))
+}
+
+/**
+ * Text 的可选中属性：支付宝小程序只支持 selectable，userSelect 需要转换为 selectable
+ * 其余平台 selectable 与 user-select 均可用，保持原样
+ */
+pub fn convert_text_select_attr_key(
+  element_name: &str,
+  miniapp_attr_name: String,
+  platform: &str,
+) -> String {
+  if element_name == TEXT_TAG && platform == "ALIPAY" && miniapp_attr_name == "user-select" {
+    return String::from("selectable");
+  }
+  miniapp_attr_name
+}
+
+// 通过 adapter 转换为模板语法的编译指令
+pub fn is_compile_directive(jsx_key: &str) -> bool {
+  [
+    COMPILE_IF,
+    COMPILE_ELSE,
+    COMPILE_FOR,
+    COMPILE_FOR_KEY,
+    COMPILE_FOR_ITEM,
+    COMPILE_FOR_INDEX,
+  ]
+  .contains(&jsx_key)
+}
+
+/**
+ * 普通属性名与当前平台的模板保留字冲突时（如百度小程序的 s-if），加上 data- 前缀进行转义
+ * 需要在 kebab-case 转换之后调用
+ */
+pub fn escape_reserved_attr_key(
+  jsx_key: &str,
+  miniapp_attr_name: String,
+  platform: &str,
+) -> String {
+  // 编译指令本身就是通过 adapter 转换为模板保留字的，不需要转义
+  let is_reserved = !is_compile_directive(jsx_key)
+    && PLATFORM_RESERVED_ATTRS
+      .iter()
+      .any(|(name, attrs)| *name == platform && attrs.contains(&miniapp_attr_name.as_str()));
+  if is_reserved {
+    return format!("data-{}", miniapp_attr_name);
+  }
+  miniapp_attr_name
+}
+
+/**
+ * 判断当前平台是否支持该事件，不在 PLATFORM_LIMITED_EVENTS 中的事件视为所有平台都支持
+ */
+pub fn is_event_supported(event_name: &str, platform: &str) -> bool {
+  PLATFORM_LIMITED_EVENTS
+    .iter()
+    .find(|(name, _)| *name == event_name)
+    .map_or(true, |(_, platforms)| platforms.contains(&platform))
+}
+
+/**
+ * 按 TagCase 转换模板中原生组件的标签名：scroll-view -> ScrollView
+ */
+pub fn convert_tag_case(name: &str, tag_case: &TagCase) -> String {
+  match tag_case {
+    TagCase::Lower => name.to_string(),
+    TagCase::Pascal => name
+      .split('-')
+      .map(|part| {
+        let mut chars = part.chars();
+        chars
+          .next()
+          .map(|c| c.to_uppercase().chain(chars).collect::<String>())
+          .unwrap_or_default()
+      })
+      .collect(),
+  }
+}
+
+/**
+ * 取值中可能带有 px 尺寸的静态属性：style、placeholder-style 等样式属性，以及 UNIT_SIZE_ATTRS
+ */
+pub fn is_unit_attr(name: &str) -> bool {
+  name == "style" || name.ends_with("-style") || UNIT_SIZE_ATTRS.contains(&name)
+}
+
+/**
+ * 按 UnitPolicy 转换静态属性值中的 px 尺寸，design_width 不大于 0 时无法换算，原样输出
+ */
+pub fn transform_unit(value: &str, policy: &UnitPolicy) -> String {
+  match policy {
+    UnitPolicy::PxToRpx { design_width } if *design_width > 0.0 => {
+      static PX_RE: OnceLock<Regex> = OnceLock::new();
+      let re = PX_RE.get_or_init(|| Regex::new(r"\b(\d+(?:\.\d+)?)px\b").unwrap());
+      re.replace_all(value, |caps: &regex::Captures| {
+        let px: f64 = caps[1].parse().unwrap();
+        let rpx = (px * 750.0 / design_width * 100000.0).round() / 100000.0;
+        format!("{}rpx", rpx)
+      })
+      .to_string()
+    }
+    _ => value.to_string(),
+  }
+}
+
+/**
+ * identify: `raw:xxx`，不做任何名称转换，原样输出为 xxx
+ */
+pub fn get_raw_attr_name(name: &JSXAttrName) -> Option<String> {
+  if let JSXAttrName::JSXNamespacedName(JSXNamespacedName { ns, name }) = name {
+    if ns.sym == COMPILE_RAW_NS {
+      return Some(name.sym.to_string());
+    }
+  }
+  None
+}
+
+/**
+ * 小程序的 mark 属性：markFooBar、mark-foo-bar、mark:fooBar 均输出为 mark:fooBar
+ * return: (JSX 中使用的属性名, 模板中的属性名)
+ */
+pub fn get_mark_attr_name(name: &JSXAttrName) -> Option<(String, String)> {
+  let mark_name = match name {
+    JSXAttrName::JSXNamespacedName(JSXNamespacedName { ns, name }) if ns.sym == MARK_NS => {
+      name.sym.to_string()
+    }
+    JSXAttrName::Ident(Ident { sym, .. }) => {
+      let sym: &str = &*sym;
+      if let Some(rest) = sym.strip_prefix("mark-") {
+        rest
+          .split('-')
+          .enumerate()
+          .map(|(idx, part)| {
+            if idx == 0 {
+              part.to_string()
+            } else {
+              let mut chars = part.chars();
+              chars
+                .next()
+                .map(|c| c.to_uppercase().chain(chars).collect())
+                .unwrap_or_default()
+            }
+          })
+          .collect()
+      } else if sym.starts_with(MARK_NS)
+        && sym
+          .chars()
+          .nth(MARK_NS.len())
+          .is_some_and(|c| c.is_uppercase())
+      {
+        // markFoo -> foo，避免误判 markers 等普通属性
+        let mut chars = sym[MARK_NS.len()..].chars();
+        chars
+          .next()
+          .map(|c| c.to_lowercase().chain(chars).collect())
+          .unwrap_or_default()
+      } else {
+        return None;
+      }
+    }
+    _ => return None,
+  };
+  if mark_name.is_empty() {
+    return None;
+  }
+  let mut chars = mark_name.chars();
+  let jsx_name = chars
+    .next()
+    .map(|c| c.to_uppercase().chain(chars).collect::<String>())
+    .unwrap_or_default();
+  Some((
+    format!("{}{}", MARK_NS, jsx_name),
+    format!("{}:{}", MARK_NS, mark_name),
+  ))
+}
+
+/**
+ * 按平台校验 Button 的 open-type，返回当前平台不支持的取值
+ * 未收录的平台不做校验；支付宝等平台可以用空格分隔多个取值
+ */
+pub fn get_invalid_open_types(value: &str, platform: &str) -> Vec<String> {
+  match BUTTON_OPEN_TYPES.iter().find(|(name, _)| *name == platform) {
+    Some((_, open_types)) => value
+      .split_whitespace()
+      .filter(|open_type| !open_types.contains(open_type))
+      .map(String::from)
+      .collect(),
+    None => vec![],
+  }
+}
+
+pub fn is_movable_passthrough_attr(element_name: &str, miniapp_attr_name: &str) -> bool {
+  MOVABLE_TAGS.contains(&element_name) && MOVABLE_PASSTHROUGH_ATTRS.contains(&miniapp_attr_name)
+}
+
 pub fn check_is_event_attr(val: &str) -> bool {
   val.starts_with("on") && val.chars().nth(2).is_some_and(|x| x.is_uppercase())
 }
 
-pub fn identify_jsx_event_key(val: &str, platform: &str) -> Option<String> {
+/**
+ * 代码生成工具等输出的全小写事件名，如 onclick，按 LOWERCASE_EVENTS 转换为 onClick
+ */
+pub fn normalize_lowercase_event_key(val: &str) -> Option<&'static str> {
+  LOWERCASE_EVENTS
+    .iter()
+    .find(|(name, _)| *name == val)
+    .map(|(_, event_name)| *event_name)
+}
+
+/**
+ * 事件绑定的前缀：adapter 中配置的 eventPrefix，未配置时为 bind
+ */
+pub fn get_event_prefix(adapter: &HashMap<String, String>) -> &str {
+  adapter
+    .get("eventPrefix")
+    .map(|prefix| prefix.as_str())
+    .unwrap_or(DEFAULT_EVENT_PREFIX)
+}
+
+pub fn identify_jsx_event_key(val: &str, platform: &str, event_prefix: &str) -> Option<String> {
   // 处理worklet事件及callback
   // 事件：     onScrollUpdateWorklet         ->  worklet:onscrollupdate
   // callback：shouldResponseOnMoveWorklet   ->  worklet:should-response-on-move
@@ -116,7 +466,7 @@ pub fn identify_jsx_event_key(val: &str, platform: &str) -> Option<String> {
         }
       }
       _ => {
-        format!("bind{}", event_name)
+        format!("{}{}", event_prefix, event_name)
       }
     };
     Some(event_binding_name)
@@ -125,6 +475,21 @@ pub fn identify_jsx_event_key(val: &str, platform: &str) -> Option<String> {
   }
 }
 
+/**
+ * 组件名统一转换为 kebab-case，与 is_inner_component 中的查找方式保持一致
+ * 这样配置中注册为 MyComp 或 my-comp 的组件都能匹配到 <MyComp />
+ */
+pub fn normalize_component_names(components: &mut HashMap<String, HashMap<String, String>>) {
+  let names: Vec<String> = components.keys().cloned().collect();
+  for name in names {
+    let kebab_name = to_kebab_case(&name);
+    if kebab_name != name && !components.contains_key(&kebab_name) {
+      let attrs = components.remove(&name).unwrap();
+      components.insert(kebab_name, attrs);
+    }
+  }
+}
+
 pub fn is_inner_component(el: &JSXElement, config: &PluginConfig) -> bool {
   let opening = &el.opening;
   if let JSXElementName::Ident(Ident { sym, .. }) = &opening.name {
@@ -195,25 +560,80 @@ pub fn create_jsx_lit_attr(name: &str, lit: Lit) -> JSXAttrOrSpread {
   })
 }
 
+pub fn create_jsx_attr(name: &str, value: JSXAttrValue) -> JSXAttrOrSpread {
+  JSXAttrOrSpread::JSXAttr(JSXAttr {
+    span,
+    name: JSXAttrName::Ident(Ident::new(name.into(), span)),
+    value: Some(value),
+  })
+}
+
 pub fn create_jsx_dynamic_id(el: &mut JSXElement, visitor: &mut TransformVisitor) -> String {
   let node_name = (visitor.get_node_name)();
 
   visitor.node_name_vec.push(node_name.clone());
-  el.opening
-    .attrs
-    .push(create_jsx_lit_attr(DYNAMIC_ID, node_name.clone().into()));
+  // 路径均从模板的根节点开始：在最近的动态祖先节点的路径后拼接当前的 childNodes 下标
+  let node_path = if visitor.node_name.is_empty() {
+    node_name.clone()
+  } else {
+    let current_path = visitor.current_node_path();
+    let ancestor_path = visitor
+      .node_paths
+      .get(current_path.root())
+      .cloned()
+      .unwrap_or_else(|| current_path.root().to_string());
+    NodePath::from_indexes(&ancestor_path, current_path.indexes()).to_string()
+  };
+  visitor.node_paths.insert(node_name.clone(), node_path);
+  el.opening.attrs.push(create_jsx_lit_attr(
+    &visitor.config.dynamic_id_attr,
+    node_name.clone().into(),
+  ));
   node_name
 }
 
+/**
+ * 为每一行添加缩进后直接写入 sink，避免为大段模板拼接中间字符串
+ */
+pub fn write_spaces_to_lines(sink: &mut impl fmt::Write, input: &str, count: usize) -> fmt::Result {
+  let spaces = " ".repeat(count);
+  for line in input.lines() {
+    writeln!(sink, "{}{}", spaces, line)?;
+  }
+  Ok(())
+}
+
 pub fn add_spaces_to_lines_with_count(input: &str, count: usize) -> String {
   let mut result = String::new();
+  write_spaces_to_lines(&mut result, input, count).unwrap();
+  result
+}
 
-  for line in input.lines() {
-    let spaces = " ".repeat(count);
-    result.push_str(&format!("{}{}\n", spaces, line));
+/**
+ * 按模板名排序，逐个把模板写入 sink，输出与依次拼接所有模板的结果一致
+ */
+pub fn write_templates(
+  templates: &HashMap<String, String>,
+  sink: &mut impl fmt::Write,
+) -> fmt::Result {
+  let mut keys: Vec<&String> = templates.keys().collect();
+  keys.sort();
+  for key in keys {
+    sink.write_str(&templates[key])?;
   }
+  Ok(())
+}
 
-  result
+/**
+ * 压缩模板：去掉每一行的缩进并删除空行
+ */
+pub fn minify_lines(input: &str) -> String {
+  input
+    .lines()
+    .map(|line| line.trim())
+    .filter(|line| !line.is_empty())
+    .collect::<Vec<&str>>()
+    .join("\n")
 }
 
 pub fn add_spaces_to_lines(input: &str) -> String {
@@ -226,15 +646,22 @@ pub fn get_harmony_replace_component_dependency_define(visitor: &mut TransformVi
   let component_set = &visitor.component_set;
   let component_replace = &visitor.config.component_replace;
   let mut harmony_component_style = String::new();
+  // 多个组件可能共用同一份依赖声明，只输出一次
+  let mut emitted_defines: HashSet<&str> = HashSet::new();
 
-  component_replace.iter().for_each(|(k, v)| {
+  // 生成的模板需要幂等，按组件名排序后再输出
+  let mut keys: Vec<&String> = component_replace.keys().collect();
+  keys.sort();
+  keys.into_iter().for_each(|k| {
     if component_set.contains(k) {
       let ComponentReplace {
         dependency_define, ..
-      } = v;
+      } = component_replace.get(k).unwrap();
 
-      harmony_component_style.push_str(dependency_define);
-      harmony_component_style.push_str("\n");
+      if emitted_defines.insert(dependency_define.as_str()) {
+        harmony_component_style.push_str(dependency_define);
+        harmony_component_style.push_str("\n");
+      }
     }
   });
 
@@ -252,19 +679,29 @@ pub fn get_harmony_component_style(visitor: &mut TransformVisitor) -> String {
     }
   };
 
-  // build_component(IMAGE_TAG, HARMONY_IMAGE_STYLE_BIND);
-  // build_component(TEXT_TAG, HARMONY_TEXT_STYLE_BIND);
   build_component(TEXT_TAG, HARMONY_TEXT_BUILDER);
   build_component(TEXT_TAG, HARMONY_TEXT_HELPER_FUNCITON);
+  build_component(IMAGE_TAG, HARMONY_IMAGE_STYLE_BIND);
+
+  // 用户为替换组件提供的 builder 函数，同样按组件名排序保证输出幂等
+  let mut keys: Vec<&String> = component_replace.keys().collect();
+  keys.sort();
+  keys.into_iter().for_each(|k| {
+    let builder_define = &component_replace.get(k).unwrap().builder_define;
+    if component_set.contains(k) && !builder_define.is_empty() {
+      harmony_component_style.push_str(builder_define);
+      harmony_component_style.push_str("\n");
+    }
+  });
 
   harmony_component_style
 }
 
-pub fn check_jsx_element_has_compile_ignore(el: &JSXElement) -> bool {
+pub fn check_jsx_element_has_attr(el: &JSXElement, attr_name: &str) -> bool {
   for attr in &el.opening.attrs {
     if let JSXAttrOrSpread::JSXAttr(JSXAttr { name, .. }) = attr {
       if let JSXAttrName::Ident(Ident { sym, .. }) = name {
-        if sym == COMPILE_IGNORE {
+        if sym == attr_name {
           return true;
         }
       }
@@ -273,8 +710,58 @@ pub fn check_jsx_element_has_compile_ignore(el: &JSXElement) -> bool {
   false
 }
 
+pub fn check_jsx_element_has_compile_ignore(el: &JSXElement) -> bool {
+  // compileIgnore="onClick,style" 只忽略列出的属性，节点本身仍然需要编译
+  el.opening.attrs.iter().any(|attr| {
+    if let JSXAttrOrSpread::JSXAttr(jsx_attr) = attr {
+      if let JSXAttrName::Ident(Ident { sym, .. }) = &jsx_attr.name {
+        return sym == COMPILE_IGNORE && get_compile_ignore_attr_list(jsx_attr).is_none();
+      }
+    }
+    false
+  })
+}
+
+fn get_compile_ignore_attr_list(jsx_attr: &JSXAttr) -> Option<HashSet<String>> {
+  if let Some(JSXAttrValue::Lit(Lit::Str(Str { value, .. }))) = &jsx_attr.value {
+    let list: HashSet<String> = value
+      .split(',')
+      .map(|name| name.trim())
+      .filter(|name| !name.is_empty())
+      .map(|name| name.to_string())
+      .collect();
+    if !list.is_empty() {
+      return Some(list);
+    }
+  }
+  None
+}
+
+/**
+ * 读取并删除 compileIgnore="onClick,style" 形式的属性，返回需要跳过编译的属性名
+ * 被跳过的属性保留在 JSX 中交给运行时处理，不输出到模板
+ */
+pub fn take_compile_ignore_attrs(opening_element: &mut JSXOpeningElement) -> HashSet<String> {
+  let mut ignored_attrs = HashSet::new();
+  opening_element.attrs.retain(|attr| {
+    if let JSXAttrOrSpread::JSXAttr(jsx_attr) = attr {
+      if let JSXAttrName::Ident(Ident { sym, .. }) = &jsx_attr.name {
+        if sym == COMPILE_IGNORE {
+          if let Some(list) = get_compile_ignore_attr_list(jsx_attr) {
+            ignored_attrs.extend(list);
+            return false;
+          }
+        }
+      }
+    }
+    true
+  });
+  ignored_attrs
+}
+
 /**
  * identify: `xx.map(function () {})` or `xx.map(() => {})`
+ * 生成的模板遍历的是运行时节点的 cn，与被遍历的数据源无关，Object.keys(obj).map、Object.entries(obj).map 等写法无需特殊处理
  */
 pub fn is_call_expr_of_loop(callee_expr: &mut Box<Expr>, args: &mut Vec<ExprOrSpread>) -> bool {
   if let Expr::Member(MemberExpr {
@@ -291,116 +778,647 @@ pub fn is_call_expr_of_loop(callee_expr: &mut Box<Expr>, args: &mut Vec<ExprOrSp
   return false;
 }
 
-pub fn is_render_fn(callee_expr: &mut Box<Expr>) -> bool {
-  fn is_starts_with_render(name: &str) -> bool {
-    name.starts_with("render")
-  }
-  match &**callee_expr {
-    Expr::Member(MemberExpr {
-      prop: MemberProp::Ident(Ident { sym: name, .. }),
-      ..
-    }) => is_starts_with_render(name),
-    Expr::Ident(Ident { sym: name, .. }) => is_starts_with_render(name),
-    _ => false,
+/**
+ * identify: `[1, 2, 3].map(...)`、`['a', 'b'].map(...)` 遍历基本类型数组字面量的循环
+ */
+pub fn is_primitive_array_loop(callee_expr: &Box<Expr>) -> bool {
+  if let Expr::Member(MemberExpr { obj, .. }) = &**callee_expr {
+    if let Expr::Array(ArrayLit { elems, .. }) = &**obj {
+      return !elems.is_empty()
+        && elems.iter().all(|elem| {
+          matches!(elem, Some(ExprOrSpread { spread: None, expr }) if matches!(
+            &**expr,
+            Expr::Lit(Lit::Str(..)) | Expr::Lit(Lit::Num(..)) | Expr::Lit(Lit::Bool(..))
+          ))
+        });
+    }
   }
+  false
 }
 
-pub fn extract_jsx_loop<'a>(
-  callee_expr: &mut Box<Expr>,
-  args: &'a mut Vec<ExprOrSpread>,
-) -> Option<&'a mut Box<JSXElement>> {
-  if is_call_expr_of_loop(callee_expr, args) {
-    if let Some(ExprOrSpread { expr, .. }) = args.get_mut(0) {
-      fn update_return_el(return_value: &mut Box<Expr>) -> Option<&mut Box<JSXElement>> {
-        if let Expr::Paren(ParenExpr { expr, .. }) = &mut **return_value {
-          *return_value = expr.take();
-        }
-        if return_value.is_jsx_element() {
-          let el = return_value.as_mut_jsx_element().unwrap();
-          el.opening.attrs.push(create_jsx_bool_attr(COMPILE_FOR));
-          el.opening.attrs.push(create_jsx_lit_attr(
-            COMPILE_FOR_KEY,
-            Lit::Str(quote_str!("sid")),
-          ));
-          return Some(el);
-        } else if return_value.is_jsx_fragment() {
-          let el = return_value.as_mut_jsx_fragment().unwrap();
-          let children = el.children.take();
-          let block_el = Box::new(JSXElement {
-            span,
-            opening: JSXOpeningElement {
-              name: JSXElementName::Ident(quote_ident!("block")),
-              span,
-              attrs: vec![
-                create_jsx_bool_attr(COMPILE_FOR),
-                create_jsx_lit_attr(COMPILE_FOR_KEY, Lit::Str(quote_str!("sid"))),
-              ],
-              self_closing: false,
-              type_args: None,
-            },
-            children,
-            closing: Some(JSXClosingElement {
-              span,
-              name: JSXElementName::Ident(quote_ident!("block")),
-            }),
-          });
-          **return_value = Expr::JSXElement(block_el);
-          return Some(return_value.as_mut_jsx_element().unwrap());
-        }
-        None
-      }
-      match &mut **expr {
-        Expr::Fn(FnExpr { function, .. }) => {
-          if let Function {
-            body: Some(BlockStmt { stmts, .. }),
+/**
+ * 判断是否为 list.map(renderItem) 形式、回调为函数引用的循环
+ * return: 回调的名称，如 renderItem、this.renderItem
+ */
+pub fn get_loop_callback_ref_name(
+  callee_expr: &Box<Expr>,
+  args: &Vec<ExprOrSpread>,
+) -> Option<String> {
+  if let Expr::Member(MemberExpr {
+    prop: MemberProp::Ident(Ident { sym, .. }),
+    ..
+  }) = &**callee_expr
+  {
+    if sym == "map" {
+      if let Some(ExprOrSpread { expr, .. }) = args.get(0) {
+        return match &**expr {
+          Expr::Ident(Ident { sym, .. }) => Some(sym.to_string()),
+          Expr::Member(MemberExpr {
+            obj,
+            prop: MemberProp::Ident(Ident { sym, .. }),
             ..
-          } = &mut **function
-          {
-            if let Some(Stmt::Return(ReturnStmt {
-              arg: Some(return_value),
-              ..
-            })) = stmts.last_mut()
-            {
-              return update_return_el(return_value);
-            }
-          }
-        }
-        Expr::Arrow(ArrowExpr { body, .. }) => match &mut **body {
-          BlockStmtOrExpr::BlockStmt(BlockStmt { stmts, .. }) => {
-            if let Some(Stmt::Return(ReturnStmt {
-              arg: Some(return_value),
-              ..
-            })) = stmts.last_mut()
-            {
-              return update_return_el(return_value);
-            }
-          }
-          BlockStmtOrExpr::Expr(return_value) => {
-            return update_return_el(return_value);
-          }
-        },
-        _ => (),
+          }) if obj.is_this() => Some(format!("this.{}", sym)),
+          _ => None,
+        };
       }
     }
   }
   None
 }
 
-pub fn get_valid_nodes(children: &Vec<JSXElementChild>) -> usize {
-  let re = Regex::new(r"^\s*$").unwrap();
-  let filtered_children: Vec<&JSXElementChild> = children
-    .iter()
-    .filter(|&item| {
-      match item {
-        JSXElementChild::JSXText(JSXText { value, .. }) => {
-          // 用正则判断value是否只含在\n和空格，如果时，返回false
-          !re.is_match(value)
+/**
+ * 判断是否为回调中返回 JSX 的 reduce 调用，如 list.reduce((acc, item) => [...acc, <View />], [])
+ * 编译模式无法把此类写法转换为模板循环，需要提示用户改用 map
+ */
+pub fn is_call_expr_of_jsx_reduce(callee_expr: &Box<Expr>, args: &Vec<ExprOrSpread>) -> bool {
+  struct JSXFinder {
+    has_jsx: bool,
+  }
+  impl Visit for JSXFinder {
+    fn visit_jsx_element(&mut self, _n: &JSXElement) {
+      self.has_jsx = true;
+    }
+    fn visit_jsx_fragment(&mut self, _n: &JSXFragment) {
+      self.has_jsx = true;
+    }
+  }
+  if let Expr::Member(MemberExpr {
+    prop: MemberProp::Ident(Ident { sym, .. }),
+    ..
+  }) = &**callee_expr
+  {
+    if sym == "reduce" {
+      if let Some(ExprOrSpread { expr, .. }) = args.get(0) {
+        if expr.is_arrow() || expr.is_fn_expr() {
+          let mut finder = JSXFinder { has_jsx: false };
+          expr.visit_with(&mut finder);
+          return finder.has_jsx;
         }
-        _ => true,
       }
-    })
-    .collect();
-  filtered_children.len()
+    }
+  }
+  false
+}
+
+/**
+ * 判断循环体的 key 是否为 extract_jsx_loop 自动补充的默认值 sid
+ * 自动补充的属性没有源码位置，以此与用户手写的 compileForKey="sid" 区分
+ */
+pub fn is_default_loop_key(el: &JSXElement) -> bool {
+  el.opening.attrs.iter().any(|attr| {
+    if let JSXAttrOrSpread::JSXAttr(JSXAttr {
+      name: JSXAttrName::Ident(Ident { sym, .. }),
+      value: Some(JSXAttrValue::Lit(Lit::Str(Str { value, .. }))),
+      span: attr_span,
+    }) = attr
+    {
+      return sym == COMPILE_FOR_KEY && value == "sid" && attr_span.is_dummy();
+    }
+    false
+  })
+}
+
+/**
+ * 循环体通过 compileForItem 重命名的 for-item，未指定时为 item
+ */
+pub fn get_loop_item_name(el: &JSXElement) -> String {
+  el.opening
+    .attrs
+    .iter()
+    .find_map(|attr| match attr {
+      JSXAttrOrSpread::JSXAttr(JSXAttr {
+        name: JSXAttrName::Ident(Ident { sym, .. }),
+        value: Some(JSXAttrValue::Lit(Lit::Str(Str { value, .. }))),
+        ..
+      }) if sym == COMPILE_FOR_ITEM => Some(value.to_string()),
+      _ => None,
+    })
+    .unwrap_or_else(|| String::from(LOOP_ITEM))
+}
+
+/**
+ * 循环体通过 compileForIndex 重命名的 for-index，未指定时为 index
+ */
+pub fn get_loop_index_name(el: &JSXElement) -> String {
+  el.opening
+    .attrs
+    .iter()
+    .find_map(|attr| match attr {
+      JSXAttrOrSpread::JSXAttr(JSXAttr {
+        name: JSXAttrName::Ident(Ident { sym, .. }),
+        value: Some(JSXAttrValue::Lit(Lit::Str(Str { value, .. }))),
+        ..
+      }) if sym == COMPILE_FOR_INDEX => Some(value.to_string()),
+      _ => None,
+    })
+    .unwrap_or_else(|| String::from(LOOP_INDEX))
+}
+
+/**
+ * 循环回调的参数名：(item 参数名, index 参数名)，解构等形式的参数为 None
+ */
+pub fn get_loop_callback_param_names(args: &Vec<ExprOrSpread>) -> (Option<String>, Option<String>) {
+  let params: Vec<&Pat> = match args.get(0).map(|arg| &*arg.expr) {
+    Some(Expr::Arrow(ArrowExpr { params, .. })) => params.iter().collect(),
+    Some(Expr::Fn(FnExpr { function, .. })) => {
+      function.params.iter().map(|param| &param.pat).collect()
+    }
+    _ => vec![],
+  };
+  let get_name = |index: usize| match params.get(index) {
+    Some(Pat::Ident(BindingIdent { id, .. })) => Some(id.sym.to_string()),
+    _ => None,
+  };
+  (get_name(0), get_name(1))
+}
+
+/**
+ * 由循环体上的 key 推导循环的 key：key={item.id} -> id，key={item} -> *this，key={index} -> {{index}}
+ * 其余表达式无法在模板中还原，返回 None
+ */
+pub fn get_loop_key_from_key_attr(
+  el: &JSXElement,
+  item_param: Option<&str>,
+  index_param: Option<&str>,
+) -> Option<String> {
+  let expr = el.opening.attrs.iter().find_map(|attr| match attr {
+    JSXAttrOrSpread::JSXAttr(JSXAttr {
+      name: JSXAttrName::Ident(Ident { sym, .. }),
+      value:
+        Some(JSXAttrValue::JSXExprContainer(JSXExprContainer {
+          expr: JSXExpr::Expr(expr),
+          ..
+        })),
+      ..
+    }) if sym == "key" => Some(expr),
+    _ => None,
+  })?;
+  match &**expr {
+    Expr::Ident(Ident { sym, .. }) if Some(sym.as_str()) == item_param => {
+      Some(String::from("*this"))
+    }
+    Expr::Ident(Ident { sym, .. }) if Some(sym.as_str()) == index_param => {
+      Some(gen_template(&get_loop_index_name(el)))
+    }
+    Expr::Member(MemberExpr {
+      obj,
+      prop: MemberProp::Ident(Ident { sym: prop, .. }),
+      ..
+    }) => match &**obj {
+      Expr::Ident(Ident { sym, .. }) if Some(sym.as_str()) == item_param => Some(prop.to_string()),
+      _ => None,
+    },
+    _ => None,
+  }
+}
+
+/**
+ * 替换循环体上的 compileForKey
+ */
+pub fn set_loop_key(el: &mut JSXElement, key: &str) {
+  el.opening.attrs.retain(|attr| {
+    !matches!(attr, JSXAttrOrSpread::JSXAttr(JSXAttr {
+      name: JSXAttrName::Ident(Ident { sym, .. }),
+      ..
+    }) if sym == COMPILE_FOR_KEY)
+  });
+  el.opening.attrs.push(create_jsx_lit_attr(
+    COMPILE_FOR_KEY,
+    Lit::Str(quote_str!(key)),
+  ));
+}
+
+/**
+ * 取出循环体上的 compileFor、compileForKey、compileForItem、compileForIndex 指令
+ */
+pub fn take_loop_directive_attrs(el: &mut JSXElement) -> Vec<JSXAttrOrSpread> {
+  let (directives, attrs): (Vec<_>, Vec<_>) =
+    el.opening.attrs.take().into_iter().partition(|attr| {
+      matches!(attr, JSXAttrOrSpread::JSXAttr(JSXAttr {
+      name: JSXAttrName::Ident(Ident { sym, .. }),
+      ..
+    }) if sym == COMPILE_FOR
+      || sym == COMPILE_FOR_KEY
+      || sym == COMPILE_FOR_ITEM
+      || sym == COMPILE_FOR_INDEX)
+    });
+  el.opening.attrs = attrs;
+  directives
+}
+
+/**
+ * 通过 compileForItem 设置循环体的 for-item 名称，覆盖已有的值
+ */
+pub fn set_loop_item_name(el: &mut JSXElement, name: &str) {
+  el.opening.attrs.retain(|attr| {
+    !matches!(attr, JSXAttrOrSpread::JSXAttr(JSXAttr {
+      name: JSXAttrName::Ident(Ident { sym, .. }),
+      ..
+    }) if sym == COMPILE_FOR_ITEM)
+  });
+  el.opening.attrs.push(create_jsx_lit_attr(
+    COMPILE_FOR_ITEM,
+    Lit::Str(quote_str!(name)),
+  ));
+}
+
+/**
+ * 判断组件属性配置是否为布尔类型，如 xs.b(i.p1,!1)
+ */
+pub fn is_boolean_attr_value(value: &str) -> bool {
+  static BOOLEAN_ATTR_RE: OnceLock<Regex> = OnceLock::new();
+  let re = BOOLEAN_ATTR_RE.get_or_init(|| Regex::new(r"^xs\.b\(.+,(!0|!1|true|false)\)$").unwrap());
+  re.is_match(value)
+}
+
+/**
+ * 布尔类型属性的动态绑定：字符串 "false" 在模板中是真值，需要按 false 处理，其余值按真假值转换
+ * 如 xs.b(i.p1,!1) -> !!xs.b(i.p1,!1)&&xs.b(i.p1,!1)!=='false'
+ */
+pub fn gen_boolean_binding(value: &str) -> String {
+  format!("!!{0}&&{0}!=='false'", value)
+}
+
+/**
+ * 循环体是否通过 compileForItem 指定了 for-item
+ */
+pub fn has_loop_item_name(el: &JSXElement) -> bool {
+  el.opening.attrs.iter().any(|attr| {
+    matches!(attr, JSXAttrOrSpread::JSXAttr(JSXAttr {
+      name: JSXAttrName::Ident(Ident { sym, .. }),
+      value: Some(JSXAttrValue::Lit(Lit::Str(_))),
+      ..
+    }) if sym == COMPILE_FOR_ITEM)
+  })
+}
+
+/**
+ * 判断循环体中手写的 {{...}} 绑定是否以变量的形式引用了 name，如 {{item.id}}，属性访问 a.item 不算
+ */
+pub fn is_loop_item_referenced(el: &JSXElement, name: &str) -> bool {
+  struct BindingFinder<'a> {
+    name: &'a str,
+    found: bool,
+  }
+  impl BindingFinder<'_> {
+    fn check(&mut self, value: &str) {
+      let is_ident_char = |c: char| c.is_alphanumeric() || c == '_' || c == '$';
+      for binding in value.split("{{").skip(1) {
+        let binding = binding.split("}}").next().unwrap_or_default();
+        for (idx, _) in binding.match_indices(self.name) {
+          let prev = binding[..idx].chars().last();
+          let next = binding[idx + self.name.len()..].chars().next();
+          if !prev.is_some_and(|c| is_ident_char(c) || c == '.') && !next.is_some_and(is_ident_char)
+          {
+            self.found = true;
+            return;
+          }
+        }
+      }
+    }
+  }
+  impl Visit for BindingFinder<'_> {
+    fn visit_jsx_attr_value(&mut self, value: &JSXAttrValue) {
+      if let JSXAttrValue::Lit(Lit::Str(Str { value, .. })) = value {
+        self.check(value);
+      }
+      value.visit_children_with(self);
+    }
+    fn visit_jsx_text(&mut self, text: &JSXText) {
+      self.check(&text.value);
+    }
+  }
+  let mut finder = BindingFinder { name, found: false };
+  el.visit_children_with(&mut finder);
+  finder.found
+}
+
+pub fn is_render_fn(callee_expr: &mut Box<Expr>, prefixes: &Vec<String>) -> bool {
+  get_render_fn_name(callee_expr, prefixes).is_some()
+}
+
+pub fn is_render_fn_name(name: &str, prefixes: &Vec<String>) -> bool {
+  prefixes
+    .iter()
+    .any(|prefix| name.starts_with(prefix.as_str()))
+}
+
+/**
+ * 判断是否为 memo(...)、forwardRef(...)、React.memo(...) 等包裹组件的调用
+ */
+pub fn is_component_wrapper_call(expr: &Expr) -> bool {
+  if let Expr::Call(CallExpr {
+    callee: Callee::Expr(callee),
+    args,
+    ..
+  }) = expr
+  {
+    let is_wrapper = match &**callee {
+      Expr::Ident(Ident { sym, .. }) => COMPONENT_WRAPPERS.contains(&sym.as_str()),
+      Expr::Member(MemberExpr {
+        obj,
+        prop: MemberProp::Ident(Ident { sym, .. }),
+        ..
+      }) => {
+        matches!(&**obj, Expr::Ident(Ident { sym: obj_sym, .. }) if obj_sym == "React")
+          && COMPONENT_WRAPPERS.contains(&sym.as_str())
+      }
+      _ => false,
+    };
+    return is_wrapper && matches!(args.get(0), Some(ExprOrSpread { spread: None, .. }));
+  }
+  false
+}
+
+/**
+ * 去掉 memo、forwardRef 的包裹，返回最内层的组件，如 memo(forwardRef(fn)) -> fn
+ */
+pub fn unwrap_component_wrapper(expr: &mut Expr) -> &mut Expr {
+  let is_wrapper = is_component_wrapper_call(expr);
+  match expr {
+    Expr::Call(CallExpr { args, .. }) if is_wrapper => unwrap_component_wrapper(&mut args[0].expr),
+    other => other,
+  }
+}
+
+/**
+ * 渲染函数调用的函数名：renderHeader()、this.renderHeader() -> renderHeader
+ */
+pub fn get_render_fn_name(callee_expr: &Expr, prefixes: &Vec<String>) -> Option<String> {
+  match callee_expr {
+    Expr::Member(MemberExpr {
+      prop: MemberProp::Ident(Ident { sym: name, .. }),
+      ..
+    })
+    | Expr::Ident(Ident { sym: name, .. })
+      if is_render_fn_name(name, prefixes) =>
+    {
+      Some(name.to_string())
+    }
+    _ => None,
+  }
+}
+
+/**
+ * 循环体为 fragment 时，取出第一个子元素上的 compileForKey。该子元素本身不是循环体，留在原处会输出无意义的 key
+ */
+fn take_first_child_loop_key(children: &mut Vec<JSXElementChild>) -> Option<JSXAttrOrSpread> {
+  let first_el = children.iter_mut().find_map(|child| match child {
+    JSXElementChild::JSXElement(el) => Some(el),
+    _ => None,
+  })?;
+  let index = first_el.opening.attrs.iter().position(|attr| {
+    matches!(attr, JSXAttrOrSpread::JSXAttr(JSXAttr {
+      name: JSXAttrName::Ident(Ident { sym, .. }),
+      ..
+    }) if sym == COMPILE_FOR_KEY)
+  })?;
+  Some(first_el.opening.attrs.remove(index))
+}
+
+pub fn extract_jsx_loop<'a>(
+  callee_expr: &mut Box<Expr>,
+  args: &'a mut Vec<ExprOrSpread>,
+) -> Option<&'a mut Box<JSXElement>> {
+  if is_call_expr_of_loop(callee_expr, args) {
+    let is_primitive_loop = is_primitive_array_loop(callee_expr);
+    if let Some(ExprOrSpread { expr, .. }) = args.get_mut(0) {
+      fn update_return_el(return_value: &mut Box<Expr>) -> Option<&mut Box<JSXElement>> {
+        // 去掉多层括号，括号内的注释不会出现在 AST 中，(/* c */ <View />) 同样适用
+        while let Expr::Paren(ParenExpr { expr, .. }) = &mut **return_value {
+          *return_value = expr.take();
+        }
+        // (track(item), <View />) 形式的逗号表达式，取最后一项作为循环体，其余表达式保留给运行时执行
+        if return_value.is_seq() {
+          let seq = return_value.as_mut_seq().unwrap();
+          return update_return_el(seq.exprs.last_mut().unwrap());
+        }
+        if return_value.is_jsx_element() {
+          let el = return_value.as_mut_jsx_element().unwrap();
+          // 预处理阶段可能已经提取过一次循环体
+          if !check_jsx_element_has_attr(el, COMPILE_FOR) {
+            el.opening.attrs.push(create_jsx_bool_attr(COMPILE_FOR));
+          }
+          // 用户可以通过 compileForKey 自行指定 key
+          if !check_jsx_element_has_attr(el, COMPILE_FOR_KEY) {
+            el.opening.attrs.push(create_jsx_lit_attr(
+              COMPILE_FOR_KEY,
+              Lit::Str(quote_str!("sid")),
+            ));
+          }
+          return Some(el);
+        } else if return_value.is_jsx_fragment() {
+          let el = return_value.as_mut_jsx_fragment().unwrap();
+          let mut children = el.children.take();
+          // 第一个子元素上指定的 compileForKey 移到 block 上作为循环的 key，否则使用默认的 sid
+          let key_attr = take_first_child_loop_key(&mut children)
+            .unwrap_or_else(|| create_jsx_lit_attr(COMPILE_FOR_KEY, Lit::Str(quote_str!("sid"))));
+          let block_el = Box::new(JSXElement {
+            span,
+            opening: JSXOpeningElement {
+              name: JSXElementName::Ident(quote_ident!(BLOCK_TAG)),
+              span,
+              attrs: vec![create_jsx_bool_attr(COMPILE_FOR), key_attr],
+              self_closing: false,
+              type_args: None,
+            },
+            children,
+            closing: Some(JSXClosingElement {
+              span,
+              name: JSXElementName::Ident(quote_ident!(BLOCK_TAG)),
+            }),
+          });
+          **return_value = Expr::JSXElement(block_el);
+          return Some(return_value.as_mut_jsx_element().unwrap());
+        }
+        None
+      }
+      // 遍历基本类型的数组字面量时，节点上没有可以区分的字段，使用 for-index 作为默认的 key
+      fn update_primitive_loop_key(el: &mut Box<JSXElement>, is_primitive_loop: bool) {
+        if is_primitive_loop && is_default_loop_key(el) {
+          let key = gen_template(&get_loop_index_name(el));
+          set_loop_key(el, &key);
+        }
+      }
+      // 回调的第二个参数名与默认的 for-index 不同时，通过 compileForIndex 输出到模板
+      fn update_index_name(el: &mut Box<JSXElement>, index_param: Option<&Pat>) {
+        if let Some(Pat::Ident(BindingIdent { id, .. })) = index_param {
+          if &*id.sym != LOOP_INDEX && !check_jsx_element_has_attr(el, COMPILE_FOR_INDEX) {
+            el.opening.attrs.push(create_jsx_lit_attr(
+              COMPILE_FOR_INDEX,
+              Lit::Str(quote_str!(id.sym.clone())),
+            ));
+          }
+        }
+      }
+      match &mut **expr {
+        Expr::Fn(FnExpr { function, .. }) => {
+          if let Function {
+            body: Some(BlockStmt { stmts, .. }),
+            params,
+            ..
+          } = &mut **function
+          {
+            if let Some(Stmt::Return(ReturnStmt {
+              arg: Some(return_value),
+              ..
+            })) = stmts.last_mut()
+            {
+              let el = update_return_el(return_value);
+              if let Some(el) = el {
+                update_index_name(el, params.get(1).map(|param| &param.pat));
+                update_primitive_loop_key(el, is_primitive_loop);
+                return Some(el);
+              }
+            }
+          }
+        }
+        Expr::Arrow(ArrowExpr { body, params, .. }) => {
+          let el = match &mut **body {
+            BlockStmtOrExpr::BlockStmt(BlockStmt { stmts, .. }) => {
+              if let Some(Stmt::Return(ReturnStmt {
+                arg: Some(return_value),
+                ..
+              })) = stmts.last_mut()
+              {
+                update_return_el(return_value)
+              } else {
+                None
+              }
+            }
+            BlockStmtOrExpr::Expr(return_value) => update_return_el(return_value),
+          };
+          if let Some(el) = el {
+            update_index_name(el, params.get(1));
+            update_primitive_loop_key(el, is_primitive_loop);
+            return Some(el);
+          }
+        }
+        _ => (),
+      }
+    }
+  }
+  None
+}
+
+/**
+ * {cond && list.map(...)}：把 compileIf 直接加到带有 compileFor 的循环体上，让同一个节点同时带有两者
+ * 循环体已有 compileIf，或条件中用到了与循环回调参数同名的变量时，无法合并，返回 false
+ */
+pub fn inject_compile_if_into_loop(condition: &Box<Expr>, expr: &mut Box<Expr>) -> bool {
+  struct IdentCollector {
+    names: HashSet<String>,
+  }
+  impl Visit for IdentCollector {
+    fn visit_ident(&mut self, ident: &Ident) {
+      self.names.insert(ident.sym.to_string());
+    }
+  }
+
+  if let Expr::Call(CallExpr {
+    callee: Callee::Expr(callee_expr),
+    args,
+    ..
+  }) = &mut **expr
+  {
+    if !is_call_expr_of_loop(callee_expr, args) {
+      return false;
+    }
+    let params: Vec<&Pat> = match args.get(0).map(|arg| &*arg.expr) {
+      Some(Expr::Arrow(ArrowExpr { params, .. })) => params.iter().collect(),
+      Some(Expr::Fn(FnExpr { function, .. })) => {
+        function.params.iter().map(|param| &param.pat).collect()
+      }
+      _ => return false,
+    };
+    let mut collector = IdentCollector {
+      names: HashSet::new(),
+    };
+    condition.visit_with(&mut collector);
+    let is_shadowed = params.iter().any(|param| match param {
+      Pat::Ident(BindingIdent { id, .. }) => collector.names.contains(id.sym.as_str()),
+      // 解构等形式的参数不便判断，保守处理
+      _ => true,
+    });
+    if is_shadowed {
+      return false;
+    }
+    if let Some(el) = extract_jsx_loop(callee_expr, args) {
+      if check_jsx_element_has_attr(el, COMPILE_IF) {
+        return false;
+      }
+      el.opening
+        .attrs
+        .push(create_jsx_expr_attr(COMPILE_IF, condition.clone()));
+      return true;
+    }
+  }
+  false
+}
+
+pub fn get_valid_nodes(children: &Vec<JSXElementChild>) -> usize {
+  children.iter().filter(|&item| is_valid_node(item)).count()
+}
+
+// 空白文本与注释不会渲染出节点
+pub fn is_valid_node(child: &JSXElementChild) -> bool {
+  match child {
+    JSXElementChild::JSXText(JSXText { value, .. }) => {
+      // 只含换行和空格的文本不会渲染出节点
+      !value.trim().is_empty()
+    }
+    // 注释 {/* ... */} 不会渲染出节点
+    JSXElementChild::JSXExprContainer(JSXExprContainer {
+      expr: JSXExpr::JSXEmptyExpr(_),
+      ..
+    }) => false,
+    _ => true,
+  }
+}
+
+/**
+ * 数组字面量中的 JSX 元素 {[<A />, <B />]} 渲染后等价于依次排列的兄弟节点，
+ * 展开为普通子节点后按顺序生成模板。数组中存在非 JSX 元素时保持原样
+ */
+pub fn expand_jsx_array_children(children: &mut Vec<JSXElementChild>) {
+  fn as_jsx_elements(child: &mut JSXElementChild) -> Option<Vec<JSXElementChild>> {
+    if let JSXElementChild::JSXExprContainer(JSXExprContainer {
+      expr: JSXExpr::Expr(expr),
+      ..
+    }) = child
+    {
+      if let Expr::Array(ArrayLit { elems, .. }) = &mut **expr {
+        let is_all_jsx = elems.iter().all(|elem| match elem {
+          Some(ExprOrSpread { spread: None, expr }) => match &**expr {
+            Expr::Paren(ParenExpr { expr, .. }) => expr.is_jsx_element(),
+            expr => expr.is_jsx_element(),
+          },
+          _ => false,
+        });
+        if !elems.is_empty() && is_all_jsx {
+          return Some(
+            elems
+              .iter_mut()
+              .filter_map(|elem| {
+                let mut expr = elem.take()?.expr;
+                if let Expr::Paren(ParenExpr { expr: e, .. }) = &mut *expr {
+                  expr = e.take();
+                }
+                (*expr).jsx_element().map(JSXElementChild::JSXElement)
+              })
+              .collect(),
+          );
+        }
+      }
+    }
+    None
+  }
+
+  *children = children
+    .take()
+    .into_iter()
+    .flat_map(|mut child| as_jsx_elements(&mut child).unwrap_or_else(|| vec![child]))
+    .collect();
 }
 
 pub fn check_jsx_element_children_exist_loop(el: &mut JSXElement) -> bool {
@@ -419,25 +1437,57 @@ pub fn check_jsx_element_child_is_loop(child: &mut JSXElementChild) -> bool {
     ..
   }) = child
   {
-    if let Expr::Call(CallExpr {
+    return check_expr_contains_loop(expr);
+  }
+  false
+}
+
+/**
+ * 判断表达式是否为循环，会穿透三元表达式的分支与逻辑表达式的右侧
+ * 如：{cond ? list.map(...) : null}、{cond && list.map(...)}
+ */
+fn check_expr_contains_loop(expr: &mut Box<Expr>) -> bool {
+  match &mut **expr {
+    Expr::Call(CallExpr {
       callee: Callee::Expr(callee_expr),
       args,
       ..
-    }) = &mut **expr
-    {
-      if is_call_expr_of_loop(callee_expr, args) {
-        return true;
-      }
+    }) => is_call_expr_of_loop(callee_expr, args),
+    Expr::Paren(ParenExpr { expr, .. }) => check_expr_contains_loop(expr),
+    Expr::Cond(CondExpr { cons, alt, .. }) => {
+      check_expr_contains_loop(cons) || check_expr_contains_loop(alt)
     }
+    Expr::Bin(BinExpr {
+      op: op!("&&") | op!("||") | op!("??"),
+      right,
+      ..
+    }) => check_expr_contains_loop(right),
+    _ => false,
   }
-  false
+}
+
+/**
+ * 鸿蒙：判断元素的子节点是否交给 createLazyChildren 在运行时渲染
+ * 1. 子节点中存在循环，且循环不是唯一的有效子节点
+ * 2. 配置了 harmony_lazy_children_threshold，且有效子节点数超过该值
+ * 其余情况逐个生成子节点
+ */
+pub fn should_render_children_lazily(el: &mut JSXElement, config: &PluginConfig) -> bool {
+  let is_loop_exist = check_jsx_element_children_exist_loop(el);
+  let el_children_len = get_valid_nodes(&el.children);
+  if is_loop_exist && el_children_len != 1 {
+    return true;
+  }
+
+  let threshold = config.harmony_lazy_children_threshold;
+  threshold > 0 && el_children_len > threshold
 }
 
 pub fn create_original_node_renderer_foreach(visitor: &mut TransformVisitor) -> String {
   add_spaces_to_lines(
     format!(
       "createLazyChildren({})",
-      visitor.get_dynmaic_node_name(visitor.get_current_node_path())
+      visitor.get_dynamic_node_name(visitor.current_node_path())
     )
     .as_str(),
   )
@@ -447,19 +1497,29 @@ pub fn create_original_node_renderer(visitor: &mut TransformVisitor) -> String {
   add_spaces_to_lines(
     format!(
       "createChildItem({} as TaroElement, createLazyChildren)",
-      visitor.get_dynmaic_node_name(visitor.get_current_node_path())
+      visitor.get_dynamic_node_name(visitor.current_node_path())
     )
     .as_str(),
   )
 }
 
+/**
+ * 字面量、变量、成员访问、模板字符串等表达式在运行时渲染为文本节点
+ */
+pub fn is_text_expr(expr: &Expr) -> bool {
+  matches!(
+    expr,
+    Expr::Lit(_) | Expr::Ident(_) | Expr::Member(_) | Expr::Tpl(_)
+  )
+}
+
 pub fn create_normal_text_template(visitor: &mut TransformVisitor, disable_this: bool) -> String {
-  let node_path = visitor.get_current_node_path();
+  let node_path = visitor.current_node_path();
 
   let node_name = if disable_this {
     String::from("item")
   } else {
-    visitor.get_dynmaic_node_name(node_path)
+    visitor.get_dynamic_node_name(node_path)
   };
 
   let code = add_spaces_to_lines(get_text_component_str(&node_name).as_str());
@@ -489,14 +1549,418 @@ pub fn is_static_jsx_element_child(jsx_element: &JSXElementChild) -> bool {
   return !visitor.has_jsx_expr;
 }
 
-pub fn gen_template(val: &str) -> String {
+/**
+ * 读取并删除子树上的 compileMode 渲染策略：static、dynamic、auto，其余取值不处理，返回 None
+ */
+pub fn take_compile_mode_strategy(el: &mut JSXElement) -> Option<String> {
+  let mut strategy = None;
+  el.opening.attrs.retain(|attr| {
+    if let JSXAttrOrSpread::JSXAttr(JSXAttr {
+      name: JSXAttrName::Ident(Ident { sym, .. }),
+      value: Some(JSXAttrValue::Lit(Lit::Str(Str { value, .. }))),
+      ..
+    }) = attr
+    {
+      if sym == COMPILE_MODE
+        && [COMPILE_MODE_STATIC, COMPILE_MODE_DYNAMIC, COMPILE_MODE_AUTO].contains(&value.as_str())
+      {
+        strategy = Some(value.to_string());
+        return false;
+      }
+    }
+    true
+  });
+  strategy
+}
+
+/**
+ * 子树中没有动态内容，且全部由内置组件组成，可以完整地编译到模板中
+ */
+pub fn is_static_subtree(el: &JSXElement, config: &PluginConfig) -> bool {
+  fn is_inner_subtree(children: &Vec<JSXElementChild>, config: &PluginConfig) -> bool {
+    children.iter().all(|child| match child {
+      JSXElementChild::JSXElement(el) => {
+        is_inner_component(el, config) && is_inner_subtree(&el.children, config)
+      }
+      JSXElementChild::JSXFragment(JSXFragment { children, .. }) => {
+        is_inner_subtree(children, config)
+      }
+      _ => true,
+    })
+  }
+  hash_static_jsx(el).is_some()
+    && is_inner_component(el, config)
+    && is_inner_subtree(&el.children, config)
+}
+
+/**
+ * 计算静态子树的结构哈希，忽略 span，结构相同的静态子树哈希相同，可用于去重
+ * 子树中存在 JSX 表达式、展开属性等动态内容时返回 None
+ */
+pub fn hash_static_jsx(el: &JSXElement) -> Option<u64> {
+  let structure = get_static_jsx_structure(el)?;
+  let mut hasher = DefaultHasher::new();
+  structure.hash(&mut hasher);
+  Some(hasher.finish())
+}
+
+/**
+ * 静态子树的结构描述，忽略 span，结构相同的静态子树描述相同
+ * 子树中存在 JSX 表达式、展开属性等动态内容时返回 None
+ */
+pub fn get_static_jsx_structure(el: &JSXElement) -> Option<String> {
+  fn write_element(el: &JSXElement, out: &mut String) -> bool {
+    let name = match &el.opening.name {
+      JSXElementName::Ident(Ident { sym, .. }) => sym.to_string(),
+      JSXElementName::JSXNamespacedName(JSXNamespacedName { ns, name }) => {
+        format!("{}:{}", ns.sym, name.sym)
+      }
+      JSXElementName::JSXMemberExpr(..) => return false,
+    };
+    let mut attrs: Vec<String> = vec![];
+    for attr in &el.opening.attrs {
+      if let JSXAttrOrSpread::JSXAttr(JSXAttr { name, value, .. }) = attr {
+        let name = match name {
+          JSXAttrName::Ident(Ident { sym, .. }) => sym.to_string(),
+          JSXAttrName::JSXNamespacedName(JSXNamespacedName { ns, name }) => {
+            format!("{}:{}", ns.sym, name.sym)
+          }
+        };
+        match value {
+          None => attrs.push(name),
+          Some(JSXAttrValue::Lit(Lit::Str(Str { value, .. }))) => {
+            attrs.push(format!("{}={:?}", name, value.as_str()))
+          }
+          _ => return false,
+        }
+      } else {
+        return false;
+      }
+    }
+    attrs.sort();
+    out.push_str(&format!("<{} {}>", name, attrs.join(" ")));
+    for child in &el.children {
+      if !write_child(child, out) {
+        return false;
+      }
+    }
+    out.push_str(&format!("</{}>", name));
+    true
+  }
+  fn write_child(child: &JSXElementChild, out: &mut String) -> bool {
+    match child {
+      JSXElementChild::JSXText(JSXText { value, .. }) => {
+        out.push_str(&jsx_text_to_string(value));
+        true
+      }
+      JSXElementChild::JSXElement(el) => write_element(el, out),
+      JSXElementChild::JSXFragment(JSXFragment { children, .. }) => {
+        out.push_str("<>");
+        let is_static = children.iter().all(|child| write_child(child, out));
+        out.push_str("</>");
+        is_static
+      }
+      _ => false,
+    }
+  }
+
+  let mut structure = String::new();
+  if !write_element(el, &mut structure) {
+    return None;
+  }
+  Some(structure)
+}
+
+/**
+ * 静态子树的模板片段能否缓存：命中缓存时不会重新生成模板，也就不会再输出提示
+ * 因此只缓存不会产生提示的子树：未配置 max_depth，且不含事件、编译指令、openType 等需要检查取值的属性
+ */
+pub fn is_cacheable_static_fragment(el: &JSXElement, config: &PluginConfig) -> bool {
+  fn has_checked_attrs(el: &JSXElement) -> bool {
+    el.opening.attrs.iter().any(|attr| match attr {
+      JSXAttrOrSpread::JSXAttr(JSXAttr {
+        name: JSXAttrName::Ident(Ident { sym, .. }),
+        ..
+      }) => {
+        sym == "openType"
+          || ["compile", "on", "catch", "bind"]
+            .iter()
+            .any(|prefix| sym.starts_with(prefix))
+      }
+      // catch:tap、bind:tap、raw:xxx 等
+      _ => true,
+    }) || el.children.iter().any(child_has_checked_attrs)
+  }
+  fn child_has_checked_attrs(child: &JSXElementChild) -> bool {
+    match child {
+      JSXElementChild::JSXElement(el) => has_checked_attrs(el),
+      JSXElementChild::JSXFragment(JSXFragment { children, .. }) => {
+        children.iter().any(child_has_checked_attrs)
+      }
+      _ => false,
+    }
+  }
+  // wxs 标签需要收集模块信息，不缓存
+  let is_xscript = matches!(
+    &el.opening.name,
+    JSXElementName::Ident(Ident { sym, .. }) if is_xscript(&to_kebab_case(sym))
+  );
+  config.max_depth == 0 && !is_xscript && is_static_subtree(el, config) && !has_checked_attrs(el)
+}
+
+/**
+ * 静态模板片段的缓存 key：结构描述加上影响输出的配置，共享缓存的 visitor 配置不同时不会互相命中
+ */
+pub fn get_static_fragment_cache_key(config: &PluginConfig, structure: &str) -> String {
+  fn sorted_attrs(attrs: &HashMap<String, String>) -> Vec<(&String, &String)> {
+    let mut attrs: Vec<_> = attrs.iter().collect();
+    attrs.sort();
+    attrs
+  }
+  fn sorted_components(
+    components: &HashMap<String, HashMap<String, String>>,
+  ) -> Vec<(&String, Vec<(&String, &String)>)> {
+    let mut components: Vec<_> = components
+      .iter()
+      .map(|(name, attrs)| (name, sorted_attrs(attrs)))
+      .collect();
+    components.sort();
+    components
+  }
+  format!(
+    "{}|{:?}|{:?}|{:?}|{:?}|{:?}\n{}",
+    config.platform,
+    config.tag_case,
+    config.unit_policy,
+    sorted_attrs(&config.adapter),
+    sorted_components(&config.components),
+    sorted_components(&config.component_default_attrs),
+    structure
+  )
+}
+
+pub fn gen_template(val: &str) -> String {
   format!("{{{{{}}}}}", val)
 }
 
+/**
+ * 循环的 key：字段名（如 sid）、*this 和已经是 {{...}} 的绑定原样输出，其余当作表达式输出为 {{...}}
+ */
+pub fn gen_loop_key(key: &str) -> String {
+  static LOOP_KEY_RE: OnceLock<Regex> = OnceLock::new();
+  let re =
+    LOOP_KEY_RE.get_or_init(|| Regex::new(r"^([A-Za-z_$][\w$]*|\*this|\{\{.+\}\})$").unwrap());
+  if re.is_match(key) {
+    key.to_string()
+  } else {
+    gen_template(key)
+  }
+}
+
+/**
+ * 跳过从 start 开始的字符串字面量，返回字符串结束后的位置
+ */
+fn skip_string_literal(chars: &[char], start: usize) -> usize {
+  let quote = chars[start];
+  let mut i = start + 1;
+  while i < chars.len() {
+    match chars[i] {
+      '\\' => i += 2,
+      c if c == quote => return i + 1,
+      _ => i += 1,
+    }
+  }
+  chars.len()
+}
+
+// ?. 后面紧跟数字时是三元表达式，如 cond?.5:1
+fn is_optional_chaining_at(chars: &[char], i: usize) -> bool {
+  chars.get(i) == Some(&'?')
+    && chars.get(i + 1) == Some(&'.')
+    && !chars.get(i + 2).is_some_and(|c| c.is_ascii_digit())
+}
+
+fn is_ident_start(c: char) -> bool {
+  c.is_ascii_alphabetic() || c == '_' || c == '$'
+}
+
+fn take_ident(chars: &[char], start: usize, out: &mut String) -> usize {
+  let mut i = start;
+  while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '_' || chars[i] == '$')
+  {
+    out.push(chars[i]);
+    i += 1;
+  }
+  i
+}
+
+// 返回与 start 处的 [ 匹配的 ] 的位置，跳过字符串字面量
+fn find_closing_bracket(chars: &[char], start: usize) -> Option<usize> {
+  let mut depth = 0;
+  let mut i = start;
+  while i < chars.len() {
+    match chars[i] {
+      '\'' | '"' => {
+        i = skip_string_literal(chars, i);
+        continue;
+      }
+      '[' => depth += 1,
+      ']' => {
+        depth -= 1;
+        if depth == 0 {
+          return Some(i);
+        }
+      }
+      _ => (),
+    }
+    i += 1;
+  }
+  None
+}
+
+/**
+ * 把可选链改写为 && 连接的形式：item?.name?.first -> (item&&item.name&&item.name.first)
+ * 成员访问链由标识符、.xxx 和 [...] 组成，如 i.cn[0].a?.b -> (i.cn[0].a&&i.cn[0].a.b)，字符串中的内容保持原样
+ */
+pub fn lower_optional_chaining(expr: &str) -> String {
+  let chars: Vec<char> = expr.chars().collect();
+  let mut result = String::new();
+  let mut i = 0;
+  while i < chars.len() {
+    let c = chars[i];
+    if c == '\'' || c == '"' {
+      let end = skip_string_literal(&chars, i);
+      result.extend(&chars[i..end]);
+      i = end;
+    } else if c.is_ascii_digit() || (is_ident_start(c) && result.ends_with('.')) {
+      // 数字字面量，以及 fn().a 等无法确定访问对象的属性名，原样输出
+      while i < chars.len()
+        && (chars[i].is_ascii_alphanumeric() || chars[i] == '_' || chars[i] == '$')
+      {
+        result.push(chars[i]);
+        i += 1;
+      }
+    } else if is_ident_start(c) {
+      // 当前已解析的访问路径，以及每个 ?. 之前需要判断的路径
+      let mut path = String::new();
+      let mut guards: Vec<String> = vec![];
+      i = take_ident(&chars, i, &mut path);
+      loop {
+        let is_optional = is_optional_chaining_at(&chars, i);
+        let next = if is_optional { i + 2 } else { i };
+        match chars.get(next) {
+          Some('.') if !is_optional && chars.get(next + 1).is_some_and(|c| is_ident_start(*c)) => {
+            path.push('.');
+            i = take_ident(&chars, next + 1, &mut path);
+          }
+          Some(c) if is_optional && is_ident_start(*c) => {
+            guards.push(path.clone());
+            path.push('.');
+            i = take_ident(&chars, next, &mut path);
+          }
+          Some('[') => match find_closing_bracket(&chars, next) {
+            Some(end) => {
+              if is_optional {
+                guards.push(path.clone());
+              }
+              let inner: String = chars[next + 1..end].iter().collect();
+              path.push_str(&format!("[{}]", lower_optional_chaining(&inner)));
+              i = end + 1;
+            }
+            None => break,
+          },
+          _ => break,
+        }
+      }
+      if guards.is_empty() {
+        result.push_str(&path);
+      } else {
+        result.push_str(&format!("({}&&{})", guards.join("&&"), path));
+      }
+    } else {
+      result.push(c);
+      i += 1;
+    }
+  }
+  result
+}
+
+/**
+ * 把空值合并改写为三元表达式：a ?? 'default' -> (a!=null?a:'default')
+ * 含有三元表达式时无法简单地确定 ?? 两侧的范围，保持原样；字符串中的 ?、?? 不做处理
+ */
+pub fn lower_nullish_coalescing(expr: &str) -> String {
+  let chars: Vec<char> = expr.chars().collect();
+  let mut nullish_at = None;
+  let mut i = 0;
+  while i < chars.len() {
+    match chars[i] {
+      '\'' | '"' => {
+        i = skip_string_literal(&chars, i);
+        continue;
+      }
+      '?' if chars.get(i + 1) == Some(&'?') => {
+        nullish_at = nullish_at.or(Some(i));
+        i += 2;
+        continue;
+      }
+      '?' if is_optional_chaining_at(&chars, i) => (),
+      '?' => return expr.to_string(),
+      _ => (),
+    }
+    i += 1;
+  }
+  match nullish_at {
+    Some(idx) => {
+      let left: String = chars[..idx].iter().collect();
+      let right: String = chars[idx + 2..].iter().collect();
+      let left = left.trim();
+      format!(
+        "({}!=null?{}:{})",
+        left,
+        left,
+        lower_nullish_coalescing(right.trim())
+      )
+    }
+    None => expr.to_string(),
+  }
+}
+
+/**
+ * 改写属性值中 {{...}} 绑定里当前平台不支持的语法
+ */
+pub fn lower_template_bindings(value: &str, platform: &str) -> String {
+  if !value.contains("{{") {
+    return value.to_string();
+  }
+  static BINDING_RE: OnceLock<Regex> = OnceLock::new();
+  let re = BINDING_RE.get_or_init(|| Regex::new(r"\{\{(.*?)\}\}").unwrap());
+  re.replace_all(value, |caps: &regex::Captures| {
+    let mut expr = caps[1].to_string();
+    if PLATFORMS_WITHOUT_OPTIONAL_CHAINING.contains(&platform) {
+      expr = lower_optional_chaining(&expr);
+    }
+    if PLATFORMS_WITHOUT_NULLISH_COALESCING.contains(&platform) {
+      expr = lower_nullish_coalescing(&expr);
+    }
+    gen_template(&expr)
+  })
+  .to_string()
+}
+
 pub fn gen_template_v(node_path: &str) -> String {
   format!("{{{{{}.v}}}}", node_path)
 }
 
+// 没有注册为内置组件的 svg、path 等 SVG 元素
+pub fn is_svg_element(el: &JSXElement, config: &PluginConfig) -> bool {
+  if let JSXElementName::Ident(Ident { sym, .. }) = &el.opening.name {
+    return SVG_TAGS.contains(&sym.as_ref()) && !is_inner_component(el, config);
+  }
+
+  false
+}
+
 pub fn is_xscript(name: &str) -> bool {
   return name == SCRIPT_TAG;
 }
@@ -530,6 +1994,184 @@ pub fn as_xscript_expr_string(
   return None;
 }
 
+/**
+ * wxs 模块的成员表达式，或者参数只包含字面量、wxs 表达式的调用表达式，可以整体在模板中求值
+ * 如 logic.a.fn(logic.b('x'), 1) -> logic.a.fn(logic.b('x'),1)
+ * quote 为字符串字面量使用的引号：属性值中为单引号，文本节点中为双引号
+ */
+pub fn as_xscript_arg_string(
+  expr: &Expr,
+  xs_module_names: &Vec<String>,
+  quote: char,
+) -> Option<String> {
+  match expr {
+    Expr::Paren(ParenExpr { expr, .. }) => as_xscript_arg_string(expr, xs_module_names, quote),
+    Expr::Member(member) => as_xscript_expr_string(member, xs_module_names),
+    Expr::Call(CallExpr {
+      callee: Callee::Expr(callee_expr),
+      args,
+      ..
+    }) => {
+      let callee = match &**callee_expr {
+        Expr::Member(member) => as_xscript_expr_string(member, xs_module_names)?,
+        _ => return None,
+      };
+      let args_string = args
+        .iter()
+        .map(|arg| {
+          if arg.spread.is_some() {
+            return None;
+          }
+          match &*arg.expr {
+            Expr::Lit(Lit::Str(Str { value, .. })) => Some(format!("{}{}{}", quote, value, quote)),
+            Expr::Lit(Lit::Num(Number { value, .. })) => Some(value.to_string()),
+            Expr::Lit(Lit::Bool(Bool { value, .. })) => Some(value.to_string()),
+            Expr::Lit(Lit::Null(_)) => Some(String::from("null")),
+            expr => as_xscript_arg_string(expr, xs_module_names, quote),
+          }
+        })
+        .collect::<Option<Vec<String>>>()?;
+      Some(format!("{}({})", callee, args_string.join(",")))
+    }
+    _ => None,
+  }
+}
+
+/**
+ * 由字符串、数字字面量和变量经 + - * / % 组成的简单表达式，可以作为内联 wxs 函数计算
+ * 变量部分以 get_name 生成的属性名交给运行时传递，记录到 attrs 中，同时作为函数的参数名
+ * 返回 (函数体中的表达式, 参数名)，如：'btn btn-' + size -> ('btn btn-'+xs0, [xs0])
+ * 不满足条件、或者不包含任何字面量时返回 None
+ */
+pub fn as_inline_expr_string(
+  expr: &Expr,
+  get_name: &mut dyn FnMut() -> String,
+  attrs: &mut Vec<JSXAttrOrSpread>,
+) -> Option<(String, Vec<String>)> {
+  // 字符串会被单引号包裹后放进 wxs 标签的源码里
+  fn is_safe_str(value: &str) -> bool {
+    !value
+      .chars()
+      .any(|c| matches!(c, '\'' | '"' | '\\' | '{' | '}' | '<' | '>' | '\n'))
+  }
+  fn is_simple_member(expr: &Expr) -> bool {
+    match expr {
+      Expr::Ident(_) | Expr::This(_) => true,
+      Expr::Member(MemberExpr {
+        obj,
+        prop: MemberProp::Ident(_),
+        ..
+      }) => is_simple_member(obj),
+      _ => false,
+    }
+  }
+  fn get_op_str(op: &BinaryOp) -> Option<&'static str> {
+    match op {
+      BinaryOp::Add => Some("+"),
+      BinaryOp::Sub => Some("-"),
+      BinaryOp::Mul => Some("*"),
+      BinaryOp::Div => Some("/"),
+      BinaryOp::Mod => Some("%"),
+      _ => None,
+    }
+  }
+  fn check(expr: &Expr, has_lit: &mut bool) -> bool {
+    match expr {
+      Expr::Lit(Lit::Str(Str { value, .. })) => {
+        *has_lit = true;
+        is_safe_str(value)
+      }
+      Expr::Lit(Lit::Num(_)) => {
+        *has_lit = true;
+        true
+      }
+      Expr::Ident(_) | Expr::Member(_) => is_simple_member(expr),
+      Expr::Paren(ParenExpr { expr, .. }) => check(expr, has_lit),
+      Expr::Bin(BinExpr {
+        op, left, right, ..
+      }) => get_op_str(op).is_some() && check(left, has_lit) && check(right, has_lit),
+      Expr::Tpl(Tpl { exprs, quasis, .. }) => {
+        let is_quasis_safe = quasis.iter().all(|quasi| match &quasi.cooked {
+          Some(cooked) => {
+            if !cooked.is_empty() {
+              *has_lit = true;
+            }
+            is_safe_str(cooked)
+          }
+          None => false,
+        });
+        is_quasis_safe && exprs.iter().all(|expr| check(expr, has_lit))
+      }
+      _ => false,
+    }
+  }
+  fn build(
+    expr: &Expr,
+    get_name: &mut dyn FnMut() -> String,
+    attrs: &mut Vec<JSXAttrOrSpread>,
+    params: &mut Vec<String>,
+  ) -> String {
+    match expr {
+      Expr::Lit(Lit::Str(Str { value, .. })) => format!("'{}'", value),
+      Expr::Lit(Lit::Num(Number { value, .. })) => value.to_string(),
+      Expr::Paren(ParenExpr { expr, .. }) => {
+        format!("({})", build(expr, get_name, attrs, params))
+      }
+      Expr::Bin(BinExpr {
+        op, left, right, ..
+      }) => format!(
+        "{}{}{}",
+        build(left, get_name, attrs, params),
+        get_op_str(op).unwrap(),
+        build(right, get_name, attrs, params)
+      ),
+      Expr::Tpl(Tpl { exprs, quasis, .. }) => {
+        // 以第一段字符串开头，保证按字符串拼接
+        let mut res = format!("'{}'", quasis[0].cooked.as_ref().unwrap());
+        for (idx, expr) in exprs.iter().enumerate() {
+          res.push_str(&format!("+({})", build(expr, get_name, attrs, params)));
+          let quasi = quasis[idx + 1].cooked.as_ref().unwrap();
+          if !quasi.is_empty() {
+            res.push_str(&format!("+'{}'", quasi));
+          }
+        }
+        res
+      }
+      _ => {
+        let name = get_name();
+        attrs.push(create_jsx_expr_attr(&name, Box::new(expr.clone())));
+        params.push(name.clone());
+        name
+      }
+    }
+  }
+
+  let mut has_lit = false;
+  if !check(expr, &mut has_lit) || !has_lit {
+    return None;
+  }
+  Some(build(expr, get_name, attrs, params))
+}
+
+/**
+ * inline_simple_exprs 生成的内联模块：<wxs module="ixs">module.exports={f0:function(xs0){return 'btn-'+xs0}}</wxs>
+ */
+pub fn create_inline_xs_module(inline_fns: &[String], adapter: &HashMap<String, String>) -> String {
+  let xs_tag = adapter.get("xs").map(|xs| xs.as_str()).unwrap_or("wxs");
+  let exports: Vec<String> = inline_fns
+    .iter()
+    .enumerate()
+    .map(|(idx, inline_fn)| format!("f{}:{}", idx, inline_fn))
+    .collect();
+  format!(
+    r#"<{} module="{}">module.exports={{{}}}</{}>"#,
+    xs_tag,
+    INLINE_XS_MODULE,
+    exports.join(","),
+    xs_tag
+  )
+}
+
 fn create_jsx_ident_opening_element(name: &str, attrs: Vec<JSXAttrOrSpread>) -> JSXOpeningElement {
   JSXOpeningElement {
     name: JSXElementName::Ident(quote_ident!(name)),
@@ -560,6 +2202,10 @@ fn create_jsx_element(
   }
 }
 
+/**
+ * 按白名单提取属性，输出顺序固定为：源码中的书写顺序（别名原地替换，不改变位置），其后是各组件补充的属性
+ * 如 scroll-view 的 type="custom"、list-builder 的 className="list-builder"
+ */
 fn extract_list_props(
   el: &mut JSXElement,
   // 需要提取的属性字段
@@ -594,12 +2240,23 @@ fn extract_list_props(
   attrs
 }
 
-fn extract_scroll_view_props(el: &mut JSXElement) -> Vec<JSXAttrOrSpread> {
+// onScrollToUpper、onScrollToLower 等事件保留原名，与普通组件一样在生成模板时转换为 bindscrolltoupper 等
+// catchScroll 等阻止冒泡的写法同样保留，onScrollCatch 等写法统一为 catchScroll，生成模板时转换为 catchscroll 等
+fn extract_scroll_view_props(
+  el: &mut JSXElement,
+  extra_scroll_view_props: &Vec<String>,
+  platform: &str,
+) -> Vec<JSXAttrOrSpread> {
   let props_alias = HashMap::from([
     ("upperThresholdCount", "upperThreshold"),
     ("lowerThresholdCount", "lowerThreshold"),
+    ("onScrollCatch", "catchScroll"),
+    ("onScrollStartCatch", "catchScrollStart"),
+    ("onScrollEndCatch", "catchScrollEnd"),
+    ("onScrollToUpperCatch", "catchScrollToUpper"),
+    ("onScrollToLowerCatch", "catchScrollToLower"),
   ]);
-  let target_attrs = HashSet::from([
+  let mut target_attrs = HashSet::from([
     "scrollX",
     "scrollY",
     "scrollTop",
@@ -613,6 +2270,16 @@ fn extract_scroll_view_props(el: &mut JSXElement) -> Vec<JSXAttrOrSpread> {
     "onScrollEnd",
     "onScrollToUpper",
     "onScrollToLower",
+    "catchScroll",
+    "catchScrollStart",
+    "catchScrollEnd",
+    "catchScrollToUpper",
+    "catchScrollToLower",
+    "onScrollCatch",
+    "onScrollStartCatch",
+    "onScrollEndCatch",
+    "onScrollToUpperCatch",
+    "onScrollToLowerCatch",
     "compileMode",
     "className",
     "cacheExtent",
@@ -620,15 +2287,93 @@ fn extract_scroll_view_props(el: &mut JSXElement) -> Vec<JSXAttrOrSpread> {
     "id",
     "key",
   ]);
+  // 平台新增的 scroll-view 属性可以通过配置加入白名单
+  target_attrs.extend(extra_scroll_view_props.iter().map(|name| name.as_str()));
   let mut attrs = extract_list_props(el, target_attrs, props_alias);
-  attrs.push(JSXAttrOrSpread::JSXAttr(JSXAttr {
-    span,
-    name: JSXAttrName::Ident(quote_ident!("type")),
-    value: Some(JSXAttrValue::Lit(Lit::Str(quote_str!("custom")))),
-  }));
+  transform_scroll_threshold_units(&mut attrs, platform);
+  inject_default_attrs(
+    &mut attrs,
+    &HashMap::from([(String::from("type"), String::from("custom"))]),
+  );
   attrs
 }
 
+/**
+ * 数字字面量的 upperThreshold、lowerThreshold 单位为 px，需要带上单位的平台转换为静态字符串，如 {50} -> "50px"
+ */
+pub fn transform_scroll_threshold_units(attrs: &mut Vec<JSXAttrOrSpread>, platform: &str) {
+  if !SCROLL_THRESHOLD_PX_PLATFORMS.contains(&platform) {
+    return;
+  }
+  attrs.iter_mut().for_each(|attr| {
+    if let JSXAttrOrSpread::JSXAttr(JSXAttr {
+      name: JSXAttrName::Ident(Ident { sym, .. }),
+      value: Some(value),
+      ..
+    }) = attr
+    {
+      if !SCROLL_THRESHOLD_ATTRS.contains(&sym.as_str()) {
+        return;
+      }
+      if let JSXAttrValue::JSXExprContainer(JSXExprContainer {
+        expr: JSXExpr::Expr(expr),
+        ..
+      }) = value
+      {
+        if let Expr::Lit(Lit::Num(Number { value: num, .. })) = &**expr {
+          *value = JSXAttrValue::Lit(Lit::Str(quote_str!(format!("{}px", num))));
+        }
+      }
+    }
+  });
+}
+
+/**
+ * 属性列表中缺少的属性以静态字符串补上默认值，作者已设置该属性、或者存在展开属性时不处理
+ */
+pub fn inject_default_attrs(
+  attrs: &mut Vec<JSXAttrOrSpread>,
+  default_attrs: &HashMap<String, String>,
+) {
+  if attrs
+    .iter()
+    .any(|attr| matches!(attr, JSXAttrOrSpread::SpreadElement(_)))
+  {
+    return;
+  }
+  let mut names: Vec<&String> = default_attrs.keys().collect();
+  names.sort();
+  for name in names {
+    let is_set = attrs.iter().any(|attr| match attr {
+      JSXAttrOrSpread::JSXAttr(JSXAttr {
+        name: JSXAttrName::Ident(Ident { sym, .. }),
+        ..
+      }) => sym == name.as_str(),
+      _ => false,
+    });
+    if !is_set {
+      attrs.push(create_jsx_lit_attr(
+        name,
+        Lit::Str(quote_str!(default_attrs[name].as_str())),
+      ));
+    }
+  }
+}
+
+/**
+ * 按配置的 component_default_attrs 为组件注入默认属性，组件名按 kebab-case 匹配
+ */
+pub fn inject_component_default_attrs(
+  el: &mut JSXElement,
+  component_default_attrs: &HashMap<String, HashMap<String, String>>,
+) {
+  if let JSXElementName::Ident(Ident { sym, .. }) = &el.opening.name {
+    if let Some(default_attrs) = component_default_attrs.get(&to_kebab_case(sym)) {
+      inject_default_attrs(&mut el.opening.attrs, default_attrs);
+    }
+  }
+}
+
 fn extract_list_builder_props(el: &mut JSXElement) -> Vec<JSXAttrOrSpread> {
   let props_alias: HashMap<&str, &str> = HashMap::from([]);
   let target_attrs = HashSet::from([
@@ -649,16 +2394,38 @@ fn extract_list_builder_props(el: &mut JSXElement) -> Vec<JSXAttrOrSpread> {
   attrs
 }
 
-pub fn transform_list_component(el: &mut JSXElement) -> () {
-  let children = el.children.clone();
+pub fn transform_list_component(
+  el: &mut JSXElement,
+  extra_scroll_view_props: &Vec<String>,
+  platform: &str,
+) -> () {
+  // slot="refresher" 的自定义下拉刷新节点属于 scroll-view，不能放进 list-builder
+  let (refresher_children, children): (Vec<JSXElementChild>, Vec<JSXElementChild>) =
+    el.children.clone().into_iter().partition(|child| {
+      if let JSXElementChild::JSXElement(child_el) = child {
+        return child_el.opening.attrs.iter().any(|attr| {
+          matches!(
+            attr,
+            JSXAttrOrSpread::JSXAttr(JSXAttr {
+              name: JSXAttrName::Ident(Ident { sym, .. }),
+              value: Some(JSXAttrValue::Lit(Lit::Str(Str { value, .. }))),
+              ..
+            }) if sym == "slot" && value == REFRESHER_SLOT
+          )
+        });
+      }
+      false
+    });
+  let mut scroll_view_children = refresher_children;
+  scroll_view_children.push(JSXElementChild::JSXElement(Box::new(create_jsx_element(
+    "list-builder",
+    extract_list_builder_props(el),
+    children,
+  ))));
   *el = create_jsx_element(
     "scroll-view",
-    extract_scroll_view_props(el),
-    vec![JSXElementChild::JSXElement(Box::new(create_jsx_element(
-      "list-builder",
-      extract_list_builder_props(el),
-      children,
-    )))],
+    extract_scroll_view_props(el, extra_scroll_view_props, platform),
+    scroll_view_children,
   )
 }
 
@@ -667,55 +2434,855 @@ pub fn transform_list_item_component(el: &mut JSXElement) -> () {
   let mut attrs = el.opening.attrs.clone();
   attrs.push(create_jsx_lit_attr(SLOT_ITEM, "item".into()));
   attrs.push(create_jsx_lit_attr("className", "list-item".into()));
+  merge_class_attrs(&mut attrs);
   *el = create_jsx_element("view", attrs, children)
 }
 
-pub fn transform_taro_components(
-  el: &mut JSXElement,
-  // 导出名和模块标识符映射关系
-  import_specifiers: &HashMap<String, String>,
-  // 导出名和别名映射关系
-  import_aliases: &HashMap<String, String>,
-) {
-  match &el.clone().opening.name {
-    JSXElementName::Ident(ident) => {
-      if let Some(import) = import_aliases.get("List") {
-        if ident.sym.as_str() == import {
-          // 检查导出模块来源
-          if let Some(src) = import_specifiers.get(import) {
-            // 如果是 @tarojs/components 导出的 List 组件，需要特殊处理
-            if src == "@tarojs/components" {
-              transform_list_component(el);
-            }
-          }
-        }
-      }
+/**
+ * SwiperItem 输出为 swiper-item，子节点原样保留，重复的 class、className 合并为一个
+ */
+pub fn transform_swiper_item_component(el: &mut JSXElement) {
+  let children = el.children.clone();
+  let mut attrs = el.opening.attrs.clone();
+  merge_class_attrs(&mut attrs);
+  *el = create_jsx_element(SWIPER_ITEM_TAG, attrs, children)
+}
 
-      if let Some(import) = import_aliases.get("ListItem") {
-        if ident.sym.as_str() == import {
-          // 检查导出模块来源
-          if let Some(src) = import_specifiers.get(import) {
-            // 如果是 @tarojs/components 导出的 ListItem 组件，需要特殊处理
-            if src == "@tarojs/components" {
-              transform_list_item_component(el);
-            }
-          }
-        }
-      }
-    }
-    _ => (),
-  };
+/**
+ * PageMeta、NavigationBar、CustomWrapper 等组件：组件名按导出名映射为对应的模板标签，避免被别名影响
+ * 属性与子节点原样保留
+ */
+pub fn transform_tag_component(el: &mut JSXElement, tag: &str) {
+  let children = el.children.clone();
+  let attrs = el.opening.attrs.clone();
+  *el = create_jsx_element(tag, attrs, children)
 }
 
-#[test]
-fn test_jsx_text() {
-  assert_eq!("   span  ", jsx_text_to_string(&"   span  ".into()));
-  assert_eq!(
-    "   s xxx   ",
-    jsx_text_to_string(&"   s\n     \n  \n   xxx   ".into())
-  );
-  assert_eq!(
-    "a   b",
+/**
+ * 判断是否为 Suspense 组件，支持 <Suspense>、<React.Suspense> 两种写法
+ */
+pub fn is_suspense_element(el: &JSXElement) -> bool {
+  match &el.opening.name {
+    JSXElementName::Ident(Ident { sym, .. }) => sym == SUSPENSE_TAG,
+    JSXElementName::JSXMemberExpr(JSXMemberExpr { prop, .. }) => prop.sym == SUSPENSE_TAG,
+    _ => false,
+  }
+}
+
+/**
+ * 把 Suspense 展开为包裹其子节点的 block，fallback 在模板中无法生效，直接丢弃
+ */
+pub fn flatten_suspense(el: &mut JSXElement) {
+  let attrs = el
+    .opening
+    .attrs
+    .take()
+    .into_iter()
+    .filter(|attr| {
+      !matches!(attr, JSXAttrOrSpread::JSXAttr(JSXAttr {
+        name: JSXAttrName::Ident(Ident { sym, .. }),
+        ..
+      }) if sym == "fallback")
+    })
+    .collect();
+  *el = create_jsx_element(BLOCK_TAG, attrs, el.children.take())
+}
+
+// 按白名单提取属性的组件都需要保留的通用属性、编译指令，具名插槽 slot 也需要透传
+fn get_common_component_attrs() -> HashSet<&'static str> {
+  HashSet::from([
+    "className",
+    "style",
+    "id",
+    "key",
+    "ref",
+    "hidden",
+    SLOT_ATTR,
+    COMPILE_MODE,
+    COMPILE_IF,
+    COMPILE_ELSE,
+    COMPILE_IGNORE,
+    COMPILE_FOR_KEY,
+    COMPILE_FOR_ITEM,
+    COMPILE_FOR_INDEX,
+    COMPILE_OBSERVE,
+  ])
+}
+
+/**
+ * 按白名单过滤组件属性时总是保留的属性：data-*、带命名空间的属性（如 mark:、raw:），以及 on*、catch*、bind* 事件
+ */
+fn is_passthrough_component_attr(name: &JSXAttrName) -> bool {
+  match name {
+    JSXAttrName::JSXNamespacedName(_) => true,
+    JSXAttrName::Ident(Ident { sym, .. }) => {
+      sym.starts_with("data-")
+        || check_is_event_attr(sym)
+        || sym.starts_with("catch")
+        || sym.starts_with("bind")
+    }
+  }
+}
+
+/**
+ * 删除不在白名单中的组件属性，返回被删除属性的位置与属性名，由调用方给出提示
+ */
+fn retain_component_attrs(
+  el: &mut JSXElement,
+  target_attrs: &HashSet<&str>,
+) -> Vec<(Span, String)> {
+  let mut removed = vec![];
+  el.opening.attrs.retain(|attr| match attr {
+    JSXAttrOrSpread::JSXAttr(JSXAttr { name, span, .. }) => {
+      if is_passthrough_component_attr(name) {
+        return true;
+      }
+      if let JSXAttrName::Ident(Ident { sym, .. }) = name {
+        if target_attrs.contains(&**sym) {
+          return true;
+        }
+        removed.push((*span, sym.to_string()));
+      }
+      false
+    }
+    JSXAttrOrSpread::SpreadElement(_) => true,
+  });
+  removed
+}
+
+/**
+ * Picker 根据 mode 删除只属于其它模式的属性并给出提示，如 date 模式下的 range，mode 为动态值或存在展开属性时无法判断，保留全部属性
+ * 其余属性、data-*、带命名空间的属性以及各类事件原样保留，onChange、onColumnChange 等事件按通用规则转换为 bindchange、bindcolumnchange
+ */
+pub fn transform_picker_component(el: &mut JSXElement) -> Vec<(Span, String)> {
+  // 展开属性无法确定具体的属性名，不做处理
+  if el
+    .opening
+    .attrs
+    .iter()
+    .any(|attr| matches!(attr, JSXAttrOrSpread::SpreadElement(_)))
+  {
+    return vec![];
+  }
+  let mode = el
+    .opening
+    .attrs
+    .iter()
+    .find_map(|attr| match attr {
+      JSXAttrOrSpread::JSXAttr(JSXAttr {
+        name: JSXAttrName::Ident(Ident { sym, .. }),
+        value,
+        ..
+      }) if sym == "mode" => match value {
+        Some(JSXAttrValue::Lit(Lit::Str(Str { value, .. }))) => Some(Some(value.to_string())),
+        _ => Some(None),
+      },
+      _ => None,
+    })
+    // 没有 mode 属性时默认为 selector
+    .unwrap_or(Some(String::from("selector")));
+  let mode = match mode {
+    Some(mode) => mode,
+    None => return vec![],
+  };
+  let mode_attrs: &[&str] = match mode.as_str() {
+    "selector" | "multiSelector" => &["range", "rangeKey", "value"],
+    "time" => &["value", "start", "end"],
+    "date" => &["value", "start", "end", "fields"],
+    "region" => &["value", "customItem", "level"],
+    _ => return vec![],
+  };
+  let mut removed = vec![];
+  el.opening.attrs.retain(|attr| match attr {
+    JSXAttrOrSpread::JSXAttr(JSXAttr {
+      name: JSXAttrName::Ident(Ident { sym, .. }),
+      span,
+      ..
+    }) if PICKER_MODE_ATTRS.contains(&&**sym) && !mode_attrs.contains(&&**sym) => {
+      removed.push((
+        *span,
+        format!("Picker mode=\"{}\" 不支持 {} 属性，已忽略", mode, sym),
+      ));
+      false
+    }
+    _ => true,
+  });
+  let children = el.children.take();
+  let attrs = el.opening.attrs.take();
+  *el = create_jsx_element(PICKER_TAG, attrs, children);
+  removed
+}
+
+/**
+ * Canvas：
+ * 1. 未指定 type 时默认为 2d
+ * 2. 2d 等新版 Canvas 通过 id 获取节点，旧版通过 canvas-id 获取，两者只写了一个时互相补全
+ * 3. onLongPress 转换为 canvas 支持的 longtap 事件
+ */
+pub fn transform_canvas_component(el: &mut JSXElement) {
+  let children = el.children.clone();
+  let mut attrs = el.opening.attrs.clone();
+  let find_attr = |attrs: &Vec<JSXAttrOrSpread>, name: &str| {
+    attrs.iter().find_map(|attr| match attr {
+      JSXAttrOrSpread::JSXAttr(JSXAttr {
+        name: JSXAttrName::Ident(Ident { sym, .. }),
+        value: Some(value),
+        ..
+      }) if sym == name => Some(value.clone()),
+      _ => None,
+    })
+  };
+
+  if find_attr(&attrs, "type").is_none() {
+    attrs.push(create_jsx_lit_attr("type", "2d".into()));
+  }
+  match (find_attr(&attrs, "id"), find_attr(&attrs, "canvasId")) {
+    (Some(id), None) => attrs.push(create_jsx_attr("canvasId", id)),
+    (None, Some(canvas_id)) => attrs.push(create_jsx_attr("id", canvas_id)),
+    _ => (),
+  }
+  attrs.iter_mut().for_each(|attr| {
+    if let JSXAttrOrSpread::JSXAttr(JSXAttr {
+      name: JSXAttrName::Ident(Ident { sym, .. }),
+      ..
+    }) = attr
+    {
+      if sym == "onLongPress" {
+        *sym = "onLongTap".into();
+      }
+    }
+  });
+  *el = create_jsx_element(CANVAS_TAG, attrs, children)
+}
+
+/**
+ * Video 删除组件不支持的属性并给出提示，data-*、带命名空间的属性以及各类事件原样保留
+ * onPlay、onTimeUpdate、onEnded 等事件按通用规则转换为 bindplay、bindtimeupdate、bindended
+ */
+pub fn transform_video_component(el: &mut JSXElement) -> Vec<(Span, String)> {
+  // 展开属性无法确定具体的属性名，不做处理
+  if el
+    .opening
+    .attrs
+    .iter()
+    .any(|attr| matches!(attr, JSXAttrOrSpread::SpreadElement(_)))
+  {
+    return vec![];
+  }
+  let mut target_attrs = get_common_component_attrs();
+  target_attrs.extend(VIDEO_ATTRS);
+  let removed = retain_component_attrs(el, &target_attrs);
+  let children = el.children.take();
+  let attrs = el.opening.attrs.take();
+  *el = create_jsx_element(VIDEO_TAG, attrs, children);
+  removed
+    .into_iter()
+    .map(|(span, name)| (span, format!("Video 不支持 {} 属性，已忽略", name)))
+    .collect()
+}
+
+fn is_class_attr(attr: &JSXAttrOrSpread) -> bool {
+  if let JSXAttrOrSpread::JSXAttr(JSXAttr {
+    name: JSXAttrName::Ident(Ident { sym, .. }),
+    ..
+  }) = attr
+  {
+    return sym == "className" || sym == "class";
+  }
+  false
+}
+
+/**
+ * 把重复出现的 class/className 合并为一个 className，值以空格拼接
+ * 全部为静态字符串时合并为字符串，存在表达式时合并为模板字符串
+ */
+pub fn merge_class_attrs(attrs: &mut Vec<JSXAttrOrSpread>) {
+  if attrs.iter().filter(|attr| is_class_attr(attr)).count() < 2 {
+    return;
+  }
+
+  let position = attrs.iter().position(is_class_attr).unwrap();
+  let mut static_classes: Vec<String> = vec![];
+  let mut dynamic_classes: Vec<Box<Expr>> = vec![];
+  attrs.retain(|attr| {
+    if !is_class_attr(attr) {
+      return true;
+    }
+    if let JSXAttrOrSpread::JSXAttr(JSXAttr { value, .. }) = attr {
+      match value {
+        Some(JSXAttrValue::Lit(Lit::Str(Str { value, .. }))) => {
+          static_classes.push(value.to_string())
+        }
+        Some(JSXAttrValue::JSXExprContainer(JSXExprContainer {
+          expr: JSXExpr::Expr(expr),
+          ..
+        })) => dynamic_classes.push(expr.clone()),
+        _ => (),
+      }
+    }
+    false
+  });
+
+  attrs.insert(position, create_class_attr(static_classes, dynamic_classes));
+}
+
+/**
+ * 用静态类名与动态类名表达式生成 className 属性
+ * 只有静态类名时生成字符串，存在动态类名时生成模板字符串 `${a} ${b} static`
+ */
+fn create_class_attr(
+  static_classes: Vec<String>,
+  dynamic_classes: Vec<Box<Expr>>,
+) -> JSXAttrOrSpread {
+  let static_class = static_classes
+    .into_iter()
+    .filter(|item| !item.is_empty())
+    .collect::<Vec<String>>()
+    .join(" ");
+  if dynamic_classes.is_empty() {
+    create_jsx_lit_attr("className", static_class.as_str().into())
+  } else {
+    // `${a} ${b} static`
+    let create_quasi = |raw: &str, tail: bool| TplElement {
+      span,
+      tail,
+      cooked: Some(raw.into()),
+      raw: raw.into(),
+    };
+    let len = dynamic_classes.len();
+    let mut quasis = vec![create_quasi("", false)];
+    for _ in 1..len {
+      quasis.push(create_quasi(" ", false));
+    }
+    let tail = if static_class.is_empty() {
+      String::new()
+    } else {
+      format!(" {}", static_class)
+    };
+    quasis.push(create_quasi(&tail, true));
+    create_jsx_expr_attr(
+      "className",
+      Box::new(Expr::Tpl(Tpl {
+        span,
+        exprs: dynamic_classes,
+        quasis,
+      })),
+    )
+  }
+}
+
+/**
+ * classnames 风格的数组 className={['a', cond && 'b']} 转换为以空格拼接的类名
+ * 字符串元素作为静态类名，cond && 'b' 转换为 cond ? 'b' : ''，其余表达式转换为 expr || ''
+ */
+pub fn transform_class_array_attrs(attrs: &mut Vec<JSXAttrOrSpread>) {
+  attrs.iter_mut().for_each(|attr| {
+    if !is_class_attr(attr) {
+      return;
+    }
+    if let JSXAttrOrSpread::JSXAttr(JSXAttr {
+      value:
+        Some(JSXAttrValue::JSXExprContainer(JSXExprContainer {
+          expr: JSXExpr::Expr(expr),
+          ..
+        })),
+      ..
+    }) = attr
+    {
+      if let Expr::Array(ArrayLit { elems, .. }) = &mut **expr {
+        // 存在展开元素、空位时无法静态拼接
+        if elems
+          .iter()
+          .any(|elem| !matches!(elem, Some(ExprOrSpread { spread: None, .. })))
+        {
+          return;
+        }
+
+        let mut static_classes: Vec<String> = vec![];
+        let mut dynamic_classes: Vec<Box<Expr>> = vec![];
+        let empty_str = || Box::new(Expr::Lit(Lit::Str(quote_str!(""))));
+        elems
+          .iter_mut()
+          .flatten()
+          .for_each(|ExprOrSpread { expr, .. }| match &mut **expr {
+            Expr::Lit(Lit::Str(Str { value, .. })) => static_classes.push(value.to_string()),
+            Expr::Bin(BinExpr {
+              op: op!("&&"),
+              left,
+              right,
+              ..
+            }) => dynamic_classes.push(Box::new(Expr::Cond(CondExpr {
+              span,
+              test: left.take(),
+              cons: right.take(),
+              alt: empty_str(),
+            }))),
+            _ => dynamic_classes.push(Box::new(Expr::Bin(BinExpr {
+              span,
+              op: op!("||"),
+              left: expr.take(),
+              right: empty_str(),
+            }))),
+          });
+        *attr = create_class_attr(static_classes, dynamic_classes);
+      }
+    }
+  });
+}
+
+/**
+ * className={classNames('a', { b: cond })} 转换为以空格拼接的类名
+ * 字符串参数作为静态类名，对象参数的每一项转换为 cond ? 'b' : ''，cond && 'b' 转换为 cond ? 'b' : ''
+ * 存在其它形式的参数时无法静态拼接，保持原样交由运行时计算
+ */
+pub fn transform_class_names_call_attrs(attrs: &mut Vec<JSXAttrOrSpread>) {
+  attrs.iter_mut().for_each(|attr| {
+    if !is_class_attr(attr) {
+      return;
+    }
+    if let JSXAttrOrSpread::JSXAttr(JSXAttr {
+      value:
+        Some(JSXAttrValue::JSXExprContainer(JSXExprContainer {
+          expr: JSXExpr::Expr(expr),
+          ..
+        })),
+      ..
+    }) = attr
+    {
+      if let Expr::Call(CallExpr {
+        callee: Callee::Expr(callee),
+        args,
+        ..
+      }) = &**expr
+      {
+        if !matches!(&**callee, Expr::Ident(Ident { sym, .. }) if CLASS_NAMES_CALLEES.contains(&&**sym))
+        {
+          return;
+        }
+
+        let mut static_classes: Vec<String> = vec![];
+        let mut dynamic_classes: Vec<Box<Expr>> = vec![];
+        let cond_class = |test: Box<Expr>, class_name: &str| {
+          Box::new(Expr::Cond(CondExpr {
+            span,
+            test,
+            cons: Box::new(Expr::Lit(Lit::Str(quote_str!(class_name)))),
+            alt: Box::new(Expr::Lit(Lit::Str(quote_str!("")))),
+          }))
+        };
+        for arg in args {
+          if arg.spread.is_some() {
+            return;
+          }
+          match &*arg.expr {
+            Expr::Lit(Lit::Str(Str { value, .. })) => static_classes.push(value.to_string()),
+            Expr::Bin(BinExpr {
+              op: op!("&&"),
+              left,
+              right,
+              ..
+            }) => match &**right {
+              Expr::Lit(Lit::Str(Str { value, .. })) => {
+                dynamic_classes.push(cond_class(left.clone(), value))
+              }
+              _ => return,
+            },
+            Expr::Object(ObjectLit { props, .. }) => {
+              for prop in props {
+                match prop {
+                  PropOrSpread::Prop(prop) => match &**prop {
+                    Prop::KeyValue(KeyValueProp { key, value }) => {
+                      let class_name = match key {
+                        PropName::Ident(Ident { sym, .. }) => sym.to_string(),
+                        PropName::Str(Str { value, .. }) => value.to_string(),
+                        _ => return,
+                      };
+                      dynamic_classes.push(cond_class(value.clone(), &class_name));
+                    }
+                    Prop::Shorthand(ident) => dynamic_classes.push(cond_class(
+                      Box::new(Expr::Ident(ident.clone())),
+                      &ident.sym,
+                    )),
+                    _ => return,
+                  },
+                  _ => return,
+                }
+              }
+            }
+            _ => return,
+          }
+        }
+        *attr = create_class_attr(static_classes, dynamic_classes);
+      }
+    }
+  });
+}
+
+/**
+ * 删除值为空字符串的 className、style，如 className="" 或 style={''}
+ * 变量等动态值即使运行时可能为空也需要保留
+ */
+pub fn remove_empty_class_style_attrs(attrs: &mut Vec<JSXAttrOrSpread>) {
+  attrs.retain(|attr| {
+    if let JSXAttrOrSpread::JSXAttr(JSXAttr {
+      name: JSXAttrName::Ident(Ident { sym, .. }),
+      value,
+      ..
+    }) = attr
+    {
+      if sym == "className" || sym == "class" || sym == STYLE_ATTR {
+        let value = match value {
+          Some(JSXAttrValue::Lit(Lit::Str(Str { value, .. }))) => Some(value),
+          Some(JSXAttrValue::JSXExprContainer(JSXExprContainer {
+            expr: JSXExpr::Expr(expr),
+            ..
+          })) => match &**expr {
+            Expr::Lit(Lit::Str(Str { value, .. })) => Some(value),
+            _ => None,
+          },
+          _ => None,
+        };
+        return !value.map_or(false, |value| value.trim().is_empty());
+      }
+    }
+    true
+  });
+}
+
+/**
+ * dangerouslySetInnerHTML={{ __html: x }} 转换为 <rich-text nodes={x} />
+ * 其余属性保留在 rich-text 上，原有子节点会被 innerHTML 覆盖因此丢弃
+ */
+pub fn transform_inner_html(el: &mut JSXElement) {
+  let mut html: Option<Box<Expr>> = None;
+  let mut attrs = el.opening.attrs.clone();
+  attrs.retain_mut(|attr| {
+    if let JSXAttrOrSpread::JSXAttr(JSXAttr {
+      name: JSXAttrName::Ident(Ident { sym, .. }),
+      value:
+        Some(JSXAttrValue::JSXExprContainer(JSXExprContainer {
+          expr: JSXExpr::Expr(expr),
+          ..
+        })),
+      ..
+    }) = attr
+    {
+      if sym != DANGEROUSLY_SET_INNER_HTML {
+        return true;
+      }
+      if let Expr::Object(ObjectLit { props, .. }) = &mut **expr {
+        if let [PropOrSpread::Prop(prop)] = props.as_mut_slice() {
+          if let Prop::KeyValue(KeyValueProp { key, value }) = &mut **prop {
+            let is_html_key = match key {
+              PropName::Ident(Ident { sym, .. }) => sym == "__html",
+              PropName::Str(Str { value, .. }) => value == "__html",
+              _ => false,
+            };
+            if is_html_key {
+              html = Some(value.take());
+              return false;
+            }
+          }
+        }
+      }
+    }
+    true
+  });
+
+  if let Some(html) = html {
+    attrs.push(create_jsx_expr_attr("nodes", html));
+    *el = create_jsx_element(RICH_TEXT_TAG, attrs, vec![]);
+  }
+}
+
+fn is_create_element_call(expr: &Expr) -> bool {
+  if let Expr::Call(CallExpr {
+    callee: Callee::Expr(callee_expr),
+    ..
+  }) = expr
+  {
+    return match &**callee_expr {
+      Expr::Member(MemberExpr {
+        prop: MemberProp::Ident(Ident { sym, .. }),
+        ..
+      }) => sym == "createElement",
+      Expr::Ident(Ident { sym, .. }) => sym == "createElement",
+      _ => false,
+    };
+  }
+  false
+}
+
+/**
+ * 判断是否为 props 中带有 compileMode 的 React.createElement 调用
+ */
+pub fn is_compile_mode_create_element(expr: &Expr) -> bool {
+  if !is_create_element_call(expr) {
+    return false;
+  }
+  let args = &expr.as_call().unwrap().args;
+  if let Some(ExprOrSpread { expr: props, .. }) = args.get(1) {
+    if let Expr::Object(ObjectLit { props, .. }) = &**props {
+      return props.iter().any(|prop| match prop {
+        PropOrSpread::Prop(prop) => match &**prop {
+          Prop::KeyValue(KeyValueProp {
+            key: PropName::Ident(Ident { sym, .. }),
+            ..
+          }) => sym == COMPILE_MODE,
+          Prop::KeyValue(KeyValueProp {
+            key: PropName::Str(Str { value, .. }),
+            ..
+          }) => value == COMPILE_MODE,
+          _ => false,
+        },
+        _ => false,
+      });
+    }
+  }
+  false
+}
+
+/**
+ * 把 React.createElement(type, props, ...children) 转换为等价的 JSX，子节点中的 createElement 一并转换
+ * 暂只支持 type 为字符串或标识符、props 为 null 或对象字面量的形式，其余情况返回 None
+ */
+pub fn create_element_to_jsx(expr: &Expr) -> Option<JSXElement> {
+  if !is_create_element_call(expr) {
+    return None;
+  }
+  let args = &expr.as_call().unwrap().args;
+  if args.iter().any(|arg| arg.spread.is_some()) {
+    return None;
+  }
+  let name = match args.get(0).map(|arg| &*arg.expr) {
+    Some(Expr::Lit(Lit::Str(Str { value, .. }))) => quote_ident!(value.clone()),
+    Some(Expr::Ident(ident)) => ident.clone(),
+    _ => return None,
+  };
+
+  let mut attrs: Vec<JSXAttrOrSpread> = vec![];
+  match args.get(1).map(|arg| &*arg.expr) {
+    None | Some(Expr::Lit(Lit::Null(_))) => (),
+    Some(Expr::Object(ObjectLit { props, .. })) => {
+      for prop in props {
+        match prop {
+          PropOrSpread::Spread(SpreadElement { expr, .. }) => {
+            attrs.push(JSXAttrOrSpread::SpreadElement(SpreadElement {
+              dot3_token: span,
+              expr: expr.clone(),
+            }));
+          }
+          PropOrSpread::Prop(prop) => match &**prop {
+            Prop::KeyValue(KeyValueProp { key, value }) => {
+              let key = match key {
+                PropName::Ident(Ident { sym, .. }) => sym.to_string(),
+                PropName::Str(Str { value, .. }) => value.to_string(),
+                _ => return None,
+              };
+              let attr = match &**value {
+                Expr::Lit(Lit::Str(str)) => create_jsx_lit_attr(&key, Lit::Str(str.clone())),
+                // { disabled: true } 等价于 <X disabled />
+                Expr::Lit(Lit::Bool(Bool { value: true, .. })) => create_jsx_bool_attr(&key),
+                _ => create_jsx_expr_attr(&key, value.clone()),
+              };
+              attrs.push(attr);
+            }
+            Prop::Shorthand(ident) => {
+              attrs.push(create_jsx_expr_attr(
+                &ident.sym,
+                Box::new(Expr::Ident(ident.clone())),
+              ));
+            }
+            _ => return None,
+          },
+        }
+      }
+    }
+    _ => return None,
+  }
+
+  let mut children: Vec<JSXElementChild> = vec![];
+  for arg in args.iter().skip(2) {
+    let child = if let Some(el) = create_element_to_jsx(&arg.expr) {
+      JSXElementChild::JSXElement(Box::new(el))
+    } else {
+      match &*arg.expr {
+        // 不含 JSX 特殊字符的字符串直接作为文本节点
+        Expr::Lit(Lit::Str(Str { value, .. }))
+          if !value.contains(|c: char| matches!(c, '{' | '}' | '<' | '>')) =>
+        {
+          JSXElementChild::JSXText(JSXText {
+            span,
+            value: value.clone(),
+            raw: value.clone(),
+          })
+        }
+        _ => JSXElementChild::JSXExprContainer(JSXExprContainer {
+          span,
+          expr: JSXExpr::Expr(arg.expr.clone()),
+        }),
+      }
+    };
+    children.push(child);
+  }
+
+  let name = JSXElementName::Ident(name);
+  let self_closing = children.is_empty();
+  Some(JSXElement {
+    span,
+    opening: JSXOpeningElement {
+      name: name.clone(),
+      span,
+      attrs,
+      self_closing,
+      type_args: None,
+    },
+    children,
+    closing: if self_closing {
+      None
+    } else {
+      Some(JSXClosingElement { span, name })
+    },
+  })
+}
+
+/**
+ * Text 中的行内条件文本 {cond && 'hello'} 包裹为嵌套的 Text：{cond && <Text>hello</Text>}
+ * 以便后续条件处理在嵌套的 text 上输出 wx:if，而不是输出三元表达式的文本
+ */
+pub fn wrap_inline_cond_text(el: &mut JSXElement) {
+  if let JSXElementName::Ident(Ident { sym, .. }) = &el.opening.name {
+    if to_kebab_case(sym) != TEXT_TAG {
+      return;
+    }
+  } else {
+    return;
+  }
+  el.children.iter_mut().for_each(|child| {
+    if let JSXElementChild::JSXExprContainer(JSXExprContainer {
+      expr: JSXExpr::Expr(expr),
+      ..
+    }) = child
+    {
+      if let Expr::Paren(ParenExpr { expr: e, .. }) = &mut **expr {
+        *expr = e.take();
+      }
+      if let Expr::Bin(BinExpr {
+        op: op!("&&"),
+        right,
+        ..
+      }) = &mut **expr
+      {
+        if let Expr::Paren(ParenExpr { expr: e, .. }) = &mut **right {
+          *right = e.take();
+        }
+        let text = match &**right {
+          Expr::Lit(Lit::Str(Str { value, .. })) => value.to_string(),
+          Expr::Lit(Lit::Num(Number { value, .. })) => value.to_string(),
+          _ => return,
+        };
+        let text_el = create_jsx_element(
+          "Text",
+          vec![],
+          vec![JSXElementChild::JSXText(JSXText {
+            span,
+            value: text.clone().into(),
+            raw: text.into(),
+          })],
+        );
+        *right = Box::new(Expr::JSXElement(Box::new(text_el)));
+      }
+    }
+  });
+}
+
+/**
+ * 从 @tarojs/components 引入、但当前平台的 components 配置中没有注册的组件
+ * return: 组件名（kebab-case）
+ */
+pub fn get_unregistered_taro_component(
+  el: &JSXElement,
+  import_specifiers: &HashMap<String, String>,
+  import_aliases: &HashMap<String, String>,
+  config: &PluginConfig,
+) -> Option<String> {
+  if let JSXElementName::Ident(Ident { sym, .. }) = &el.opening.name {
+    let local_name = sym.to_string();
+    if import_specifiers.get(&local_name).map(|src| src.as_str()) != Some("@tarojs/components") {
+      return None;
+    }
+    // import { X as Y } 时按原始导出名 X 查找
+    let export_name = import_aliases
+      .iter()
+      .find(|(_, alias)| **alias == local_name)
+      .map(|(export_name, _)| export_name.as_str())
+      .unwrap_or(&local_name);
+    let name = to_kebab_case(export_name);
+    if !config.components.contains_key(&name) {
+      return Some(name);
+    }
+  }
+  None
+}
+
+pub fn transform_taro_components(
+  el: &mut JSXElement,
+  // 导出名和模块标识符映射关系
+  import_specifiers: &HashMap<String, String>,
+  // 导出名和别名映射关系
+  import_aliases: &HashMap<String, String>,
+  // 额外透传给 scroll-view 的 List 属性
+  extra_scroll_view_props: &Vec<String>,
+  platform: &str,
+) -> Vec<(Span, String)> {
+  match &el.clone().opening.name {
+    JSXElementName::Ident(ident) => {
+      // 检查当前组件是否为 @tarojs/components 导出的对应组件
+      let is_taro_component = |export_name: &str| {
+        if let Some(import) = import_aliases.get(export_name) {
+          if ident.sym.as_str() == import {
+            if let Some(src) = import_specifiers.get(import) {
+              return src == "@tarojs/components";
+            }
+          }
+        }
+        false
+      };
+
+      if is_taro_component("List") {
+        transform_list_component(el, extra_scroll_view_props, platform);
+      } else if is_taro_component("ListItem") {
+        transform_list_item_component(el);
+      } else if is_taro_component("SwiperItem") {
+        transform_swiper_item_component(el);
+      } else if is_taro_component("PageMeta") {
+        transform_tag_component(el, PAGE_META_TAG);
+      } else if is_taro_component("NavigationBar") {
+        transform_tag_component(el, NAVIGATION_BAR_TAG);
+      } else if is_taro_component("CustomWrapper") {
+        transform_tag_component(el, CUSTOM_WRAPPER_TAG);
+      } else if is_taro_component("Picker") {
+        return transform_picker_component(el);
+      } else if is_taro_component("Canvas") {
+        transform_canvas_component(el);
+      } else if is_taro_component("Video") {
+        return transform_video_component(el);
+      }
+    }
+    _ => (),
+  };
+  vec![]
+}
+
+#[test]
+fn test_jsx_text() {
+  assert_eq!("   span  ", jsx_text_to_string(&"   span  ".into()));
+  assert_eq!(
+    "   s xxx   ",
+    jsx_text_to_string(&"   s\n     \n  \n   xxx   ".into())
+  );
+  assert_eq!(
+    "a   b",
     jsx_text_to_string(&"   \n    \n   a   b  \n   ".into())
   );
   assert_eq!(
@@ -724,3 +3291,458 @@ fn test_jsx_text() {
   );
   assert_eq!("", jsx_text_to_string(&"".into()));
 }
+
+#[test]
+fn test_convert_jsx_attr_key() {
+  let adapter = HashMap::from([(String::from("if"), String::from("wx:if"))]);
+  assert_eq!("class", convert_jsx_attr_key("className", &adapter));
+  assert_eq!("slot", convert_jsx_attr_key("slot", &adapter));
+  assert_eq!("hover-class", convert_jsx_attr_key("hoverClass", &adapter));
+  assert_eq!("wx:if", convert_jsx_attr_key(COMPILE_IF, &adapter));
+}
+
+#[test]
+fn test_default_adapter() {
+  let adapter = default_adapter("WEAPP");
+  assert_eq!("wx:if", adapter["if"]);
+  assert_eq!("wx:elif", adapter["elseif"]);
+  assert_eq!("wx:for-item", adapter["forItem"]);
+  assert_eq!("wxs", adapter["xs"]);
+
+  let adapter = default_adapter("ALIPAY");
+  assert_eq!("a:if", adapter["if"]);
+  assert_eq!("a:for-index", adapter["forIndex"]);
+  assert_eq!("a:key", adapter["key"]);
+  assert_eq!("sjs", adapter["xs"]);
+  assert_eq!("alipay", adapter["type"]);
+
+  assert_eq!("s-if", default_adapter("SWAN")["if"]);
+  assert!(default_adapter("UNKNOWN").is_empty());
+}
+
+#[test]
+fn test_lower_template_bindings() {
+  assert_eq!(
+    "{{(item&&item.name&&item.name.first)}}",
+    lower_template_bindings("{{item?.name?.first}}", "WEAPP")
+  );
+  assert_eq!(
+    "a-{{(i.a&&i.a.b.c)}}-{{x}}",
+    lower_template_bindings("a-{{i.a?.b.c}}-{{x}}", "ALIPAY")
+  );
+  assert_eq!(
+    "{{cond?1:2}}",
+    lower_template_bindings("{{cond?1:2}}", "WEAPP")
+  );
+  assert_eq!(
+    "{{item?.name}}",
+    lower_template_bindings("{{item?.name}}", "OTHER")
+  );
+  assert_eq!("item?.name", lower_template_bindings("item?.name", "WEAPP"));
+}
+
+#[test]
+fn test_lower_optional_chaining() {
+  assert_eq!(
+    "(i.cn[0].a&&i.cn[0].a.b)",
+    lower_optional_chaining("i.cn[0].a?.b")
+  );
+  assert_eq!(
+    "(a&&a[key]&&a[key].b)",
+    lower_optional_chaining("a?.[key]?.b")
+  );
+  assert_eq!("list[(i&&i.idx)]", lower_optional_chaining("list[i?.idx]"));
+  assert_eq!(
+    "'a?.b' + (c&&c.d)",
+    lower_optional_chaining("'a?.b' + c?.d")
+  );
+  assert_eq!("cond?.5:1", lower_optional_chaining("cond?.5:1"));
+}
+
+#[test]
+fn test_lower_nullish_coalescing() {
+  assert_eq!(
+    "{{(label!=null?label:'default')}}",
+    lower_template_bindings("{{label ?? 'default'}}", "WEAPP")
+  );
+  assert_eq!(
+    "(a!=null?a:(b!=null?b:c))",
+    lower_nullish_coalescing("a ?? b ?? c")
+  );
+  assert_eq!("c ? a ?? b : d", lower_nullish_coalescing("c ? a ?? b : d"));
+  assert_eq!(
+    "(url!=null?url:'http://a.com')",
+    lower_nullish_coalescing("url ?? 'http://a.com'")
+  );
+  assert_eq!("'a??b'", lower_nullish_coalescing("'a??b'"));
+  assert_eq!(
+    "{{label ?? 'default'}}",
+    lower_template_bindings("{{label ?? 'default'}}", "OTHER")
+  );
+}
+
+#[test]
+fn test_escape_reserved_attr_key() {
+  let adapter = HashMap::from([(String::from("if"), String::from("s-if"))]);
+  let key = convert_jsx_attr_key("sIf", &adapter);
+  assert_eq!(
+    "data-s-if",
+    escape_reserved_attr_key("sIf", key.clone(), "SWAN")
+  );
+  assert_eq!("s-if", escape_reserved_attr_key("sIf", key, "WEAPP"));
+  let key = convert_jsx_attr_key(COMPILE_IF, &adapter);
+  assert_eq!("s-if", escape_reserved_attr_key(COMPILE_IF, key, "SWAN"));
+  let key = convert_jsx_attr_key("hoverClass", &adapter);
+  assert_eq!(
+    "hover-class",
+    escape_reserved_attr_key("hoverClass", key, "SWAN")
+  );
+}
+
+#[test]
+fn test_gen_loop_key() {
+  assert_eq!("sid", gen_loop_key("sid"));
+  assert_eq!("*this", gen_loop_key("*this"));
+  assert_eq!("{{item.id}}", gen_loop_key("item.id"));
+  assert_eq!("{{index + 1}}", gen_loop_key("index + 1"));
+  assert_eq!("{{index}}", gen_loop_key("{{index}}"));
+}
+
+#[test]
+fn test_merge_class_of_list_item() {
+  let get_class_attrs = |el: &JSXElement| -> Vec<JSXAttrOrSpread> {
+    el.opening
+      .attrs
+      .iter()
+      .filter(|attr| is_class_attr(attr))
+      .cloned()
+      .collect()
+  };
+
+  let mut el = create_jsx_element(
+    "ListItem",
+    vec![create_jsx_lit_attr("className", "my-item".into())],
+    vec![],
+  );
+  transform_list_item_component(&mut el);
+  let class_attrs = get_class_attrs(&el);
+  assert_eq!(1, class_attrs.len());
+  assert_eq!(
+    class_attrs[0],
+    create_jsx_lit_attr("className", "my-item list-item".into())
+  );
+
+  let mut el = create_jsx_element(
+    "ListItem",
+    vec![create_jsx_expr_attr(
+      "className",
+      Box::new(Expr::Ident(quote_ident!("myClass"))),
+    )],
+    vec![],
+  );
+  transform_list_item_component(&mut el);
+  let class_attrs = get_class_attrs(&el);
+  assert_eq!(1, class_attrs.len());
+  if let JSXAttrOrSpread::JSXAttr(JSXAttr {
+    value:
+      Some(JSXAttrValue::JSXExprContainer(JSXExprContainer {
+        expr: JSXExpr::Expr(expr),
+        ..
+      })),
+    ..
+  }) = &class_attrs[0]
+  {
+    let tpl = expr.as_tpl().unwrap();
+    assert_eq!(1, tpl.exprs.len());
+    assert_eq!("", &*tpl.quasis[0].raw);
+    assert_eq!(" list-item", &*tpl.quasis[1].raw);
+  } else {
+    panic!("className should be merged into a template literal");
+  }
+}
+
+#[test]
+fn test_get_valid_nodes() {
+  let text = |value: &str| {
+    JSXElementChild::JSXText(JSXText {
+      span,
+      value: value.into(),
+      raw: value.into(),
+    })
+  };
+  let comment = JSXElementChild::JSXExprContainer(JSXExprContainer {
+    span,
+    expr: JSXExpr::JSXEmptyExpr(JSXEmptyExpr { span }),
+  });
+  let element = JSXElementChild::JSXElement(Box::new(create_jsx_element("view", vec![], vec![])));
+
+  assert_eq!(
+    0,
+    get_valid_nodes(&vec![text("\n  "), comment.clone(), text("\n")])
+  );
+  assert_eq!(
+    1,
+    get_valid_nodes(&vec![text("\n  "), comment.clone(), element.clone()])
+  );
+  assert_eq!(2, get_valid_nodes(&vec![text("hello"), comment, element]));
+}
+
+#[test]
+fn test_check_jsx_element_child_is_loop() {
+  let ident = |name: &str| Box::new(Expr::Ident(quote_ident!(name)));
+  let loop_expr = || {
+    Box::new(Expr::Call(CallExpr {
+      span,
+      callee: Callee::Expr(Box::new(Expr::Member(MemberExpr {
+        span,
+        obj: ident("list"),
+        prop: MemberProp::Ident(quote_ident!("map")),
+      }))),
+      args: vec![ExprOrSpread {
+        spread: None,
+        expr: Box::new(Expr::Arrow(ArrowExpr {
+          span,
+          params: vec![],
+          body: Box::new(BlockStmtOrExpr::Expr(ident("item"))),
+          is_async: false,
+          is_generator: false,
+          type_params: None,
+          return_type: None,
+        })),
+      }],
+      type_args: None,
+    }))
+  };
+  let child = |expr: Box<Expr>| {
+    JSXElementChild::JSXExprContainer(JSXExprContainer {
+      span,
+      expr: JSXExpr::Expr(expr),
+    })
+  };
+
+  assert!(check_jsx_element_child_is_loop(&mut child(loop_expr())));
+  assert!(check_jsx_element_child_is_loop(&mut child(Box::new(
+    Expr::Cond(CondExpr {
+      span,
+      test: ident("cond"),
+      cons: loop_expr(),
+      alt: Box::new(Expr::Lit(Lit::Null(Null { span }))),
+    })
+  ))));
+  assert!(check_jsx_element_child_is_loop(&mut child(Box::new(
+    Expr::Cond(CondExpr {
+      span,
+      test: ident("cond"),
+      cons: ident("placeholder"),
+      alt: loop_expr(),
+    })
+  ))));
+  assert!(check_jsx_element_child_is_loop(&mut child(Box::new(
+    Expr::Bin(BinExpr {
+      span,
+      op: op!("&&"),
+      left: ident("cond"),
+      right: loop_expr(),
+    })
+  ))));
+  assert!(!check_jsx_element_child_is_loop(&mut child(Box::new(
+    Expr::Cond(CondExpr {
+      span,
+      test: ident("cond"),
+      cons: ident("a"),
+      alt: ident("b"),
+    })
+  ))));
+}
+
+#[test]
+fn test_transform_class_array_attrs() {
+  let str_expr = |value: &str| Box::new(Expr::Lit(Lit::Str(quote_str!(value))));
+  let elem = |expr: Box<Expr>| Some(ExprOrSpread { spread: None, expr });
+  let class_array = |elems: Vec<Option<ExprOrSpread>>| {
+    vec![create_jsx_expr_attr(
+      "className",
+      Box::new(Expr::Array(ArrayLit { span, elems })),
+    )]
+  };
+
+  let mut attrs = class_array(vec![elem(str_expr("a")), elem(str_expr("b"))]);
+  transform_class_array_attrs(&mut attrs);
+  assert_eq!(attrs, vec![create_jsx_lit_attr("className", "a b".into())]);
+
+  let mut attrs = class_array(vec![
+    elem(str_expr("a")),
+    elem(Box::new(Expr::Bin(BinExpr {
+      span,
+      op: op!("&&"),
+      left: Box::new(Expr::Ident(quote_ident!("cond"))),
+      right: str_expr("b"),
+    }))),
+  ]);
+  transform_class_array_attrs(&mut attrs);
+  if let JSXAttrOrSpread::JSXAttr(JSXAttr {
+    value:
+      Some(JSXAttrValue::JSXExprContainer(JSXExprContainer {
+        expr: JSXExpr::Expr(expr),
+        ..
+      })),
+    ..
+  }) = &attrs[0]
+  {
+    let Tpl { exprs, quasis, .. } = expr.as_tpl().unwrap();
+    assert_eq!(
+      quasis
+        .iter()
+        .map(|quasi| quasi.raw.to_string())
+        .collect::<Vec<_>>(),
+      vec!["", " a"]
+    );
+    assert_eq!(
+      exprs,
+      &vec![Box::new(Expr::Cond(CondExpr {
+        span,
+        test: Box::new(Expr::Ident(quote_ident!("cond"))),
+        cons: str_expr("b"),
+        alt: str_expr(""),
+      }))]
+    );
+  } else {
+    panic!("className should be a template literal");
+  }
+}
+
+#[test]
+fn test_hash_static_jsx() {
+  let text = |value: &str| {
+    JSXElementChild::JSXText(JSXText {
+      span,
+      value: value.into(),
+      raw: value.into(),
+    })
+  };
+  let card = |title: &str| {
+    create_jsx_element(
+      "view",
+      vec![create_jsx_lit_attr("class", "card".into())],
+      vec![JSXElementChild::JSXElement(Box::new(create_jsx_element(
+        "text",
+        vec![],
+        vec![text(title)],
+      )))],
+    )
+  };
+
+  let mut moved = card("hello");
+  moved.span = swc_core::common::Span::new(
+    swc_core::common::BytePos(10),
+    swc_core::common::BytePos(42),
+    Default::default(),
+  );
+  assert!(hash_static_jsx(&card("hello")).is_some());
+  assert_eq!(hash_static_jsx(&card("hello")), hash_static_jsx(&moved));
+  assert_ne!(
+    hash_static_jsx(&card("hello")),
+    hash_static_jsx(&card("world"))
+  );
+
+  let dynamic = create_jsx_element(
+    "view",
+    vec![create_jsx_expr_attr(
+      "class",
+      Box::new(Expr::Ident(quote_ident!("cls"))),
+    )],
+    vec![],
+  );
+  assert_eq!(hash_static_jsx(&dynamic), None);
+}
+
+#[test]
+fn test_normalize_lowercase_event_key() {
+  assert_eq!(Some("onClick"), normalize_lowercase_event_key("onclick"));
+  assert_eq!(
+    Some("onTouchStart"),
+    normalize_lowercase_event_key("ontouchstart")
+  );
+  assert_eq!(None, normalize_lowercase_event_key("onClick"));
+  assert_eq!(None, normalize_lowercase_event_key("online"));
+}
+
+#[test]
+fn test_get_mark_attr_name() {
+  let ident = |name: &str| JSXAttrName::Ident(quote_ident!(name));
+  let expected = Some((String::from("markFooBar"), String::from("mark:fooBar")));
+  assert_eq!(expected, get_mark_attr_name(&ident("markFooBar")));
+  assert_eq!(expected, get_mark_attr_name(&ident("mark-foo-bar")));
+  assert_eq!(
+    expected,
+    get_mark_attr_name(&JSXAttrName::JSXNamespacedName(JSXNamespacedName {
+      ns: quote_ident!("mark"),
+      name: quote_ident!("fooBar"),
+    }))
+  );
+  assert_eq!(None, get_mark_attr_name(&ident("markers")));
+  assert_eq!(None, get_mark_attr_name(&ident("mark")));
+}
+
+#[test]
+fn test_convert_tag_case() {
+  assert_eq!(
+    "scroll-view",
+    convert_tag_case("scroll-view", &TagCase::Lower)
+  );
+  assert_eq!(
+    "ScrollView",
+    convert_tag_case("scroll-view", &TagCase::Pascal)
+  );
+  assert_eq!("View", convert_tag_case("view", &TagCase::Pascal));
+}
+
+#[test]
+fn test_get_invalid_open_types() {
+  assert!(get_invalid_open_types("getUserInfo", "WEAPP").is_empty());
+  assert_eq!(
+    vec!["getUserinfo"],
+    get_invalid_open_types("getUserinfo", "WEAPP")
+  );
+  assert_eq!(
+    vec!["getUserInfo"],
+    get_invalid_open_types("getUserInfo", "ALIPAY")
+  );
+  assert!(get_invalid_open_types("anything", "UNKNOWN").is_empty());
+}
+
+#[test]
+fn test_minify_lines() {
+  assert_eq!(
+    "Column() {\nText('a')\n}",
+    minify_lines("  Column() {\n\n    Text('a')\n  }\n")
+  );
+}
+
+#[test]
+fn test_transform_unit() {
+  let policy = UnitPolicy::PxToRpx {
+    design_width: 375.0,
+  };
+  assert_eq!("width: 200rpx", transform_unit("width: 100px", &policy));
+  assert_eq!(
+    "width: 100px",
+    transform_unit("width: 100px", &UnitPolicy::PxToRpx { design_width: 0.0 })
+  );
+  assert!(is_unit_attr("style"));
+  assert!(is_unit_attr("placeholder-style"));
+  assert!(is_unit_attr("previous-margin"));
+  assert!(!is_unit_attr("hover-class"));
+}
+
+#[test]
+fn test_gen_boolean_binding() {
+  assert!(is_boolean_attr_value("xs.b(i.p1,!1)"));
+  assert!(is_boolean_attr_value("xs.b(i.p4,false)"));
+  assert!(!is_boolean_attr_value("xs.b(i.p1,'none')"));
+  assert!(!is_boolean_attr_value("i.cl"));
+  assert_eq!(
+    "!!xs.b(i.p1,!1)&&xs.b(i.p1,!1)!=='false'",
+    gen_boolean_binding("xs.b(i.p1,!1)")
+  );
+}
@@ -0,0 +1,11 @@
+const TARO_TEMPLATES_f0t0 = '<template name="tmpl_0_f0t0"><view><text slot="header">{{i.cn[0].cn[0].v}}</text><video slot="footer" src="{{i.cn[1].src}}"></video></view></template>';
+import { Video } from '@tarojs/components';
+function Index() {
+    return <View compileMode="f0t0">
+
+            <Text>{title}</Text>
+
+            <video src={src}></video>
+
+          </View>;
+}
@@ -98,12 +98,7 @@ export default struct TARO_TEMPLATES_f0t0 {
       .onAreaChange(getComponentEventCallback(this.node0.childNodes[4] as TaroElement, AREA_CHANGE_EVENT_NAME, (res: TaroAny) => {
         (this.node0.childNodes[4] as TaroElement)._nodeInfo.areaInfo = res[1]
       }))
-      .borderRadius({
-        topLeft: (this.node0.childNodes[4] as TaroElement)._st.hmStyle.borderTopLeftRadius,
-        topRight: (this.node0.childNodes[4] as TaroElement)._st.hmStyle.borderTopRightRadius,
-        bottomLeft: (this.node0.childNodes[4] as TaroElement)._st.hmStyle.borderBottomLeftRadius,
-        bottomRight: (this.node0.childNodes[4] as TaroElement)._st.hmStyle.borderBottomRightRadius
-      })
+      .imageStyleBind(this.node0.childNodes[4] as TaroElement)
       .onComplete(e => { eventHandler(e, 'complete', this.node0.childNodes[4] as TaroElement) } )
       Column() {}
       .attributeModifier(columnModify.setNode(this.node0.childNodes[5] as TaroElement))
@@ -121,6 +116,16 @@ export default struct TARO_TEMPLATES_f0t0 {
     }))
   }
 }
+
+@Extend(Image)
+function imageStyleBind (node: TaroElement) {
+  .borderRadius({
+    topLeft: node._st.hmStyle.borderTopLeftRadius,
+    topRight: node._st.hmStyle.borderTopRightRadius,
+    bottomLeft: node._st.hmStyle.borderBottomLeftRadius,
+    bottomRight: node._st.hmStyle.borderBottomRightRadius
+  })
+}
 `;
 function Index() {
     return <View compileMode="f0t0" _dynamicID="node0">
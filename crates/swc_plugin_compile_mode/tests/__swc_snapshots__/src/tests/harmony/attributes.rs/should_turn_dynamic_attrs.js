@@ -106,12 +106,7 @@ export default struct TARO_TEMPLATES_f0t0 {
       .onAreaChange(getComponentEventCallback(this.node0.childNodes[1] as TaroElement, AREA_CHANGE_EVENT_NAME, (res: TaroAny) => {
         (this.node0.childNodes[1] as TaroElement)._nodeInfo.areaInfo = res[1]
       }))
-      .borderRadius({
-        topLeft: (this.node0.childNodes[1] as TaroElement)._st.hmStyle.borderTopLeftRadius,
-        topRight: (this.node0.childNodes[1] as TaroElement)._st.hmStyle.borderTopRightRadius,
-        bottomLeft: (this.node0.childNodes[1] as TaroElement)._st.hmStyle.borderBottomLeftRadius,
-        bottomRight: (this.node0.childNodes[1] as TaroElement)._st.hmStyle.borderBottomRightRadius
-      })
+      .imageStyleBind(this.node0.childNodes[1] as TaroElement)
       Image((this.node5 as TaroElement).getAttribute('src'))
       .objectFit(getImageMode((this.node5 as TaroElement).getAttribute('mode')))
       .attributeModifier(commonStyleModify.setNode(this.node5 as TaroElement))
@@ -119,12 +114,7 @@ export default struct TARO_TEMPLATES_f0t0 {
       .onAreaChange(getComponentEventCallback(this.node5 as TaroElement, AREA_CHANGE_EVENT_NAME, (res: TaroAny) => {
         (this.node5 as TaroElement)._nodeInfo.areaInfo = res[1]
       }))
-      .borderRadius({
-        topLeft: (this.node5 as TaroElement)._st.hmStyle.borderTopLeftRadius,
-        topRight: (this.node5 as TaroElement)._st.hmStyle.borderTopRightRadius,
-        bottomLeft: (this.node5 as TaroElement)._st.hmStyle.borderBottomLeftRadius,
-        bottomRight: (this.node5 as TaroElement)._st.hmStyle.borderBottomRightRadius
-      })
+      .imageStyleBind(this.node5 as TaroElement)
     }
     .attributeModifier(columnModify.setNode(this.node0 as TaroElement))
     .onVisibleAreaChange(getNodeThresholds(this.node0 as TaroElement) || [0.0, 1.0], getComponentEventCallback(this.node0 as TaroElement, VISIBLE_CHANGE_EVENT_NAME))
@@ -133,6 +123,16 @@ export default struct TARO_TEMPLATES_f0t0 {
     }))
   }
 }
+
+@Extend(Image)
+function imageStyleBind (node: TaroElement) {
+  .borderRadius({
+    topLeft: node._st.hmStyle.borderTopLeftRadius,
+    topRight: node._st.hmStyle.borderTopRightRadius,
+    bottomLeft: node._st.hmStyle.borderBottomLeftRadius,
+    bottomRight: node._st.hmStyle.borderBottomRightRadius
+  })
+}
 `;
 function Index() {
     return <View compileMode="f0t0" _dynamicID="node0">
@@ -76,12 +76,7 @@ export default struct TARO_TEMPLATES_f0t0 {
             .onAreaChange(getComponentEventCallback(this.node3 as TaroElement, AREA_CHANGE_EVENT_NAME, (res: TaroAny) => {
               (this.node3 as TaroElement)._nodeInfo.areaInfo = res[1]
             }))
-            .borderRadius({
-              topLeft: (this.node3 as TaroElement)._st.hmStyle.borderTopLeftRadius,
-              topRight: (this.node3 as TaroElement)._st.hmStyle.borderTopRightRadius,
-              bottomLeft: (this.node3 as TaroElement)._st.hmStyle.borderBottomLeftRadius,
-              bottomRight: (this.node3 as TaroElement)._st.hmStyle.borderBottomRightRadius
-            })
+            .imageStyleBind(this.node3 as TaroElement)
             if ((this.node2.childNodes[1] as TaroElement)._attrs.compileIf) {
               Image((this.node4 as TaroElement).getAttribute('src'))
               .objectFit(getImageMode((this.node4 as TaroElement).getAttribute('mode')))
@@ -90,12 +85,7 @@ export default struct TARO_TEMPLATES_f0t0 {
               .onAreaChange(getComponentEventCallback(this.node4 as TaroElement, AREA_CHANGE_EVENT_NAME, (res: TaroAny) => {
                 (this.node4 as TaroElement)._nodeInfo.areaInfo = res[1]
               }))
-              .borderRadius({
-                topLeft: (this.node4 as TaroElement)._st.hmStyle.borderTopLeftRadius,
-                topRight: (this.node4 as TaroElement)._st.hmStyle.borderTopRightRadius,
-                bottomLeft: (this.node4 as TaroElement)._st.hmStyle.borderBottomLeftRadius,
-                bottomRight: (this.node4 as TaroElement)._st.hmStyle.borderBottomRightRadius
-              })
+              .imageStyleBind(this.node4 as TaroElement)
             }
           }
           .attributeModifier(columnModify.setNode(this.node2 as TaroElement))
@@ -119,6 +109,16 @@ export default struct TARO_TEMPLATES_f0t0 {
     }))
   }
 }
+
+@Extend(Image)
+function imageStyleBind (node: TaroElement) {
+  .borderRadius({
+    topLeft: node._st.hmStyle.borderTopLeftRadius,
+    topRight: node._st.hmStyle.borderTopRightRadius,
+    bottomLeft: node._st.hmStyle.borderBottomLeftRadius,
+    bottomRight: node._st.hmStyle.borderBottomRightRadius
+  })
+}
 `;
 function Index() {
     return <View className="goods" compileMode="f0t0" _dynamicID="node0">
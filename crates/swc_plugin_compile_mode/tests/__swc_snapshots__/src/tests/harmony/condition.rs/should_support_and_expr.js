@@ -96,11 +96,7 @@ export default struct TARO_TEMPLATES_f0t0 {
           (this.node3 as TaroElement)._nodeInfo.areaInfo = res[1]
         }))
       }
-      if ((this.node0.childNodes[3] as TaroElement)._attrs.compileIf) {
-        createText(this.node0.childNodes[3] as TaroTextElement)
-      } else {
-        createText(this.node0.childNodes[3] as TaroTextElement)
-      }
+      createText(this.node0.childNodes[3] as TaroTextElement)
       Column() {}
       .attributeModifier(columnModify.setNode(this.node4 as TaroElement))
       .onVisibleAreaChange(getNodeThresholds(this.node4 as TaroElement) || [0.0, 1.0], getComponentEventCallback(this.node4 as TaroElement, VISIBLE_CHANGE_EVENT_NAME))
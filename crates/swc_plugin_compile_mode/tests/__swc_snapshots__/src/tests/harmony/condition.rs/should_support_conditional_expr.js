@@ -162,11 +162,7 @@ export default struct TARO_TEMPLATES_f0t0 {
           (this.node10 as TaroElement)._nodeInfo.areaInfo = res[1]
         }))
       }
-      if ((this.node0.childNodes[6] as TaroElement)._attrs.compileIf) {
-        createText(this.node0.childNodes[6] as TaroTextElement)
-      } else {
-        createText(this.node0.childNodes[6] as TaroTextElement)
-      }
+      createText(this.node0.childNodes[6] as TaroTextElement)
       Column() {}
       .attributeModifier(columnModify.setNode(this.node11 as TaroElement))
       .onVisibleAreaChange(getNodeThresholds(this.node11 as TaroElement) || [0.0, 1.0], getComponentEventCallback(this.node11 as TaroElement, VISIBLE_CHANGE_EVENT_NAME))
@@ -76,12 +76,7 @@ export default struct TARO_TEMPLATES_f0t0 {
         .onAreaChange(getComponentEventCallback(this.node0.childNodes[1].childNodes[0] as TaroElement, AREA_CHANGE_EVENT_NAME, (res: TaroAny) => {
           (this.node0.childNodes[1].childNodes[0] as TaroElement)._nodeInfo.areaInfo = res[1]
         }))
-        .borderRadius({
-          topLeft: (this.node0.childNodes[1].childNodes[0] as TaroElement)._st.hmStyle.borderTopLeftRadius,
-          topRight: (this.node0.childNodes[1].childNodes[0] as TaroElement)._st.hmStyle.borderTopRightRadius,
-          bottomLeft: (this.node0.childNodes[1].childNodes[0] as TaroElement)._st.hmStyle.borderBottomLeftRadius,
-          bottomRight: (this.node0.childNodes[1].childNodes[0] as TaroElement)._st.hmStyle.borderBottomRightRadius
-        })
+        .imageStyleBind(this.node0.childNodes[1].childNodes[0] as TaroElement)
       }
       .attributeModifier(columnModify.setNode(this.node0.childNodes[1] as TaroElement))
       .onVisibleAreaChange(getNodeThresholds(this.node0.childNodes[1] as TaroElement) || [0.0, 1.0], getComponentEventCallback(this.node0.childNodes[1] as TaroElement, VISIBLE_CHANGE_EVENT_NAME))
@@ -96,6 +91,16 @@ export default struct TARO_TEMPLATES_f0t0 {
     }))
   }
 }
+
+@Extend(Image)
+function imageStyleBind (node: TaroElement) {
+  .borderRadius({
+    topLeft: node._st.hmStyle.borderTopLeftRadius,
+    topRight: node._st.hmStyle.borderTopRightRadius,
+    bottomLeft: node._st.hmStyle.borderBottomLeftRadius,
+    bottomRight: node._st.hmStyle.borderBottomRightRadius
+  })
+}
 `;
 function Index() {
     return <View compileMode="f0t0" _dynamicID="node0">
@@ -68,14 +68,19 @@ export default struct TARO_TEMPLATES_f0t0 {
     .onAreaChange(getComponentEventCallback(this.node0 as TaroElement, AREA_CHANGE_EVENT_NAME, (res: TaroAny) => {
       (this.node0 as TaroElement)._nodeInfo.areaInfo = res[1]
     }))
-    .borderRadius({
-      topLeft: (this.node0 as TaroElement)._st.hmStyle.borderTopLeftRadius,
-      topRight: (this.node0 as TaroElement)._st.hmStyle.borderTopRightRadius,
-      bottomLeft: (this.node0 as TaroElement)._st.hmStyle.borderBottomLeftRadius,
-      bottomRight: (this.node0 as TaroElement)._st.hmStyle.borderBottomRightRadius
-    })
+    .imageStyleBind(this.node0 as TaroElement)
   }
 }
+
+@Extend(Image)
+function imageStyleBind (node: TaroElement) {
+  .borderRadius({
+    topLeft: node._st.hmStyle.borderTopLeftRadius,
+    topRight: node._st.hmStyle.borderTopRightRadius,
+    bottomLeft: node._st.hmStyle.borderBottomLeftRadius,
+    bottomRight: node._st.hmStyle.borderBottomRightRadius
+  })
+}
 `;
 const TARO_TEMPLATES_f0t1 = `import {
   rowModify,
@@ -0,0 +1,5 @@
+const TARO_TEMPLATES_f0t0 = '<template name="tmpl_0_f0t0"><template is="{{xs.a(c, i.nn, l)}}" data="{{i:i,c:c+1,l:xs.f(l,i.nn)}}" /></template>';
+import { List } from '@tarojs/components';
+function Index() {
+    return <scroll-view compileMode="f0t0" lowerThreshold={20} catchScroll={handleScroll} className="feed" scrollY type="custom"><list-builder type="static" childCount={list.length} padding={gap} className="list-builder"></list-builder></scroll-view>;
+}